@@ -0,0 +1,101 @@
+use near_sdk::{env, near_bindgen, AccountId, Promise, PromiseError, PromiseOrValue, PromiseResult};
+
+use crate::models::{
+    claim::PendingClaim,
+    contract::{Faucet, FaucetExt},
+    events::{EventLog, EventLogVariant, FaucetClaimIneligibleLog, FaucetClaimLog},
+    ft_request::external::{cross_joychi, StorageBalance},
+    game_request::external::PetOwnerStats,
+};
+use crate::{ATTACHED_STORAGE_DEPOSIT, ATTACHED_TRANSFER_FT, GAS_FOR_CROSS_CALL, GAS_FOR_RESOLVE_CLAIM};
+
+#[near_bindgen]
+impl Faucet {
+    // Claim the JOY of the tier the account falls in, or log why it can't claim
+    #[private]
+    pub fn resolve_eligibility(
+        &mut self,
+        #[callback_result] owner_stats: Result<PetOwnerStats, PromiseError>,
+        account_id: AccountId,
+        relayer_id: Option<AccountId>,
+    ) -> PromiseOrValue<bool> {
+        let (pet_count, amount) = match owner_stats {
+            Ok(owner_stats) => (Some(owner_stats.pet_count), self.internal_eligible_amount(owner_stats.pet_count)),
+            Err(_) => (None, None),
+        };
+
+        if let Some(amount) = amount {
+            return PromiseOrValue::Promise(self.internal_start_claim(&account_id, amount, relayer_id, None));
+        }
+
+        let reason = match pet_count {
+            Some(pet_count) => format!("No drip for accounts with {} pets", pet_count),
+            None => "Game state unavailable".to_string(),
+        };
+        EventLog::faucet(EventLogVariant::FaucetClaimIneligible(vec![FaucetClaimIneligibleLog {
+            account_id: account_id.to_string(),
+            pet_count,
+            reason,
+        }]))
+        .emit();
+
+        PromiseOrValue::Value(false)
+    }
+
+    // Register the account if needed, then transfer the JOY
+    #[private]
+    pub fn resolve_storage_check(
+        &mut self,
+        #[callback_result] storage_balance: Result<Option<StorageBalance>, PromiseError>,
+        claim: PendingClaim,
+    ) -> Promise {
+        let is_registered = matches!(storage_balance, Ok(Some(_)));
+
+        let transfer = cross_joychi::ext(self.ft_address.to_owned())
+            .with_static_gas(GAS_FOR_CROSS_CALL)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .ft_transfer(claim.account_id.clone(), claim.amount);
+
+        let transfer = if is_registered {
+            transfer
+        } else {
+            cross_joychi::ext(self.ft_address.to_owned())
+                .with_static_gas(GAS_FOR_CROSS_CALL)
+                .with_attached_deposit(ATTACHED_STORAGE_DEPOSIT)
+                .storage_deposit(claim.account_id.clone())
+                .then(transfer)
+        };
+
+        transfer.then(
+            Self::ext(env::current_account_id())
+                .with_static_gas(GAS_FOR_RESOLVE_CLAIM)
+                .resolve_claim(claim, !is_registered),
+        )
+    }
+
+    // The claim stays counted only if the JOY was transferred
+    #[private]
+    pub fn resolve_claim(&mut self, claim: PendingClaim, registered: bool) -> bool {
+        let success = matches!(env::promise_result(0), PromiseResult::Successful(_));
+        if !success {
+            self.internal_revert_claim(&claim);
+        } else if self.claims_by_account.get(&claim.account_id).unwrap().claim_count == 1 {
+            self.internal_first_claim_drips(&claim.account_id);
+        }
+
+        let log = FaucetClaimLog {
+            account_id: claim.account_id.to_string(),
+            amount: claim.amount,
+            relayer_id: claim.relayer_id.map(|relayer_id| relayer_id.to_string()),
+            registered,
+        };
+        let event = if success {
+            EventLogVariant::FaucetClaim(vec![log])
+        } else {
+            EventLogVariant::FaucetClaimFailed(vec![log])
+        };
+        EventLog::faucet(event).emit();
+
+        success
+    }
+}
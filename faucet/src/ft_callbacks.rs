@@ -0,0 +1,26 @@
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_sdk::{env, json_types::U128, log, near_bindgen, AccountId, PromiseOrValue};
+
+use crate::models::contract::{Faucet, FaucetExt};
+
+#[near_bindgen]
+impl FungibleTokenReceiver for Faucet {
+    // Anyone can fund the faucet with ft_transfer_call, the msg is ignored.
+    // It keeps its name, near_bindgen reads the argument under the parameter name
+    #[allow(unused_variables)]
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        assert!(
+            env::predecessor_account_id() == self.ft_address,
+            "Only JOY is accepted"
+        );
+
+        log!("{} funded the faucet with {}", sender_id, amount.0);
+
+        PromiseOrValue::Value(U128(0))
+    }
+}
@@ -1,13 +1,36 @@
+use ed25519_dalek::Verifier;
 use models::{
-    contract::{Faucet, FaucetExt},
+    claim::{ClaimInfo, ClaimStatus, FaucetBudget, PendingClaim},
+    contract::{Faucet, FaucetExt, FaucetStorageKey},
+    drip::{FaucetInfo, StarterItem},
+    eligibility::{EligibilityTier, FaucetEligibility},
     ft_request::external::cross_joychi,
+    game_request::external::cross_joychi_game,
+    nft_request::external::{cross_item_nft, TokenMetadata},
+    voucher::Voucher,
 };
-use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Gas};
+use near_sdk::borsh::BorshSerialize;
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedSet};
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, CurveType, Gas, Promise, PublicKey};
+mod callbacks;
+mod ft_callbacks;
+mod migrate;
 pub mod models;
 
 pub const GAS_FOR_CROSS_CALL: Gas = Gas(3_000_000_000_000);
 pub const ATTACHED_TRANSFER_FT: u128 = 1;
 pub const ATTACHED_STORAGE_DEPOSIT: u128 = 1_250_000_000_000_000_000_000;
+pub const ONE_DAY: u64 = 86_400_000_000_000;
+pub const GAS_FOR_STORAGE_BALANCE: Gas = Gas(5_000_000_000_000);
+pub const GAS_FOR_RESOLVE_STORAGE_CHECK: Gas = Gas(60_000_000_000_000);
+pub const GAS_FOR_RESOLVE_CLAIM: Gas = Gas(30_000_000_000_000);
+pub const GAS_FOR_NFT_MINT: Gas = Gas(15_000_000_000_000);
+pub const GAS_FOR_PET_OWNER_STATS: Gas = Gas(5_000_000_000_000);
+pub const GAS_FOR_RESOLVE_ELIGIBILITY: Gas = Gas(80_000_000_000_000);
+pub const ATTACHED_DEPOSIT_NFT: u128 = 100_000_000_000_000_000_000_000;
+// 100 JOY
+pub const DEFAULT_FAUCET_AMOUNT: u128 = 100_000_000_000_000_000_000_000_000;
 
 #[near_bindgen]
 impl Faucet {
@@ -20,23 +43,70 @@ impl Faucet {
             owner_id,
             ft_address,
             is_active: is_ative,
-            amount: U128(10)
+            amount: U128(DEFAULT_FAUCET_AMOUNT),
+            cooldown: ONE_DAY,
+            lifetime_cap: U128(0),
+            daily_budget: U128(0),
+            day_start: env::block_timestamp(),
+            claimed_today: U128(0),
+            claims_by_account: LookupMap::new(FaucetStorageKey::ClaimsByAccount.try_to_vec().unwrap()),
+            relayers: UnorderedSet::new(FaucetStorageKey::Relayers.try_to_vec().unwrap()),
+            voucher_public_key: None,
+            used_voucher_nonces: LookupSet::new(FaucetStorageKey::UsedVoucherNonces.try_to_vec().unwrap()),
+            near_drip: U128(0),
+            starter_item: None,
+            starter_items_minted: 0,
+            game_address: None,
+            eligibility_tiers: Vec::new(),
         }
     }
 
-    pub fn get_joychi(&mut self, addr_to: AccountId) {
+    // The JOY goes to the caller, relayers claim for addr_to.
+    // Once the backend key is set, the claim needs a voucher signed by the backend and gives its amount,
+    // otherwise with eligibility tiers set the amount depends on the pets the account owns in the game
+    pub fn get_joychi(&mut self, addr_to: Option<AccountId>, voucher: Option<Voucher>, signature: Option<Base64VecU8>) -> Promise {
         assert!(self.is_active, "faucet's not ative");
         assert!(self.amount.0 > 0, "Owner must set faucet amount");
-        cross_joychi::ext(self.ft_address.to_owned())
-            .with_static_gas(GAS_FOR_CROSS_CALL)
-            .with_attached_deposit(ATTACHED_STORAGE_DEPOSIT)
-            .storage_deposit(addr_to.clone())
-            .then(
-                cross_joychi::ext(self.ft_address.to_owned())
-                    .with_static_gas(GAS_FOR_CROSS_CALL)
-                    .with_attached_deposit(ATTACHED_TRANSFER_FT)
-                    .ft_transfer(addr_to.clone(), self.amount),
+
+        let caller = env::predecessor_account_id();
+        let is_relayer = self.relayers.contains(&caller);
+        let addr_to = if is_relayer {
+            addr_to.expect("Relayers must set addr_to")
+        } else {
+            assert!(
+                addr_to.is_none() || addr_to.as_ref() == Some(&caller),
+                "Only relayers can claim for another account"
             );
+            caller.clone()
+        };
+
+        let (amount, voucher_nonce) = match &self.voucher_public_key {
+            Some(_) => {
+                let voucher = voucher.expect("Voucher is required");
+                assert!(voucher.account_id == addr_to, "Voucher is for another account");
+                self.internal_use_voucher(&voucher, signature.expect("Voucher signature is required"));
+                (voucher.amount, Some(voucher.nonce))
+            }
+            None => (self.amount, None),
+        };
+
+        let relayer_id = if is_relayer { Some(caller) } else { None };
+
+        match &self.game_address {
+            Some(game_address) if voucher_nonce.is_none() && !self.eligibility_tiers.is_empty() => {
+                self.internal_assert_cooldown(&addr_to);
+
+                cross_joychi_game::ext(game_address.to_owned())
+                    .with_static_gas(GAS_FOR_PET_OWNER_STATS)
+                    .get_pet_owner_stats(addr_to.clone())
+                    .then(
+                        Self::ext(env::current_account_id())
+                            .with_static_gas(GAS_FOR_RESOLVE_ELIGIBILITY)
+                            .resolve_eligibility(addr_to, relayer_id),
+                    )
+            }
+            _ => self.internal_start_claim(&addr_to, amount.0, relayer_id, voucher_nonce),
+        }
     }
 
     pub fn set_faucet_amount(&mut self, amount: U128){
@@ -46,4 +116,309 @@ impl Faucet {
 
     }
 
+    pub fn set_active(&mut self, is_active: bool) {
+        assert!(self.owner_id == env::signer_account_id(), " Not Owner");
+        self.is_active = is_active;
+    }
+
+    // The NEAR and the starter item are given on the first claim of an account, 0 and None turn them off
+    pub fn set_drips(&mut self, near_drip: U128, starter_item: Option<StarterItem>) {
+        assert!(self.owner_id == env::signer_account_id(), " Not Owner");
+        self.near_drip = near_drip;
+        self.starter_item = starter_item;
+    }
+
+    pub fn withdraw_joy(&mut self, receiver_id: AccountId, amount: U128) -> Promise {
+        assert!(self.owner_id == env::signer_account_id(), " Not Owner");
+        cross_joychi::ext(self.ft_address.to_owned())
+            .with_static_gas(GAS_FOR_CROSS_CALL)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .ft_transfer(receiver_id, amount)
+    }
+
+    pub fn withdraw_near(&mut self, receiver_id: AccountId, amount: U128) -> Promise {
+        assert!(self.owner_id == env::signer_account_id(), " Not Owner");
+        Promise::new(receiver_id).transfer(amount.0)
+    }
+
+    // None as game_address or no tiers turns the eligibility check off
+    pub fn set_eligibility(&mut self, game_address: Option<AccountId>, tiers: Vec<EligibilityTier>) {
+        assert!(self.owner_id == env::signer_account_id(), " Not Owner");
+        let mut tiers = tiers;
+        tiers.sort_by_key(|tier| tier.max_pets);
+        self.game_address = game_address;
+        self.eligibility_tiers = tiers;
+    }
+
+    pub fn get_eligibility(&self) -> FaucetEligibility {
+        FaucetEligibility {
+            game_address: self.game_address.clone(),
+            tiers: self.eligibility_tiers.clone(),
+        }
+    }
+
+    pub fn get_faucet_info(&self) -> FaucetInfo {
+        FaucetInfo {
+            is_active: self.is_active,
+            amount: self.amount,
+            near_drip: self.near_drip,
+            starter_item: self.starter_item.clone(),
+        }
+    }
+
+    // A lifetime cap or a daily budget of 0 turns it off
+    pub fn set_claim_limits(&mut self, cooldown: u64, lifetime_cap: U128, daily_budget: U128) {
+        assert!(self.owner_id == env::signer_account_id(), " Not Owner");
+        self.cooldown = cooldown;
+        self.lifetime_cap = lifetime_cap;
+        self.daily_budget = daily_budget;
+    }
+
+    pub fn add_relayer(&mut self, account_id: AccountId) {
+        assert!(self.owner_id == env::signer_account_id(), " Not Owner");
+        self.relayers.insert(&account_id);
+    }
+
+    pub fn remove_relayer(&mut self, account_id: AccountId) {
+        assert!(self.owner_id == env::signer_account_id(), " Not Owner");
+        self.relayers.remove(&account_id);
+    }
+
+    // None turns the vouchers off
+    pub fn set_voucher_public_key(&mut self, public_key: Option<PublicKey>) {
+        assert!(self.owner_id == env::signer_account_id(), " Not Owner");
+        self.voucher_public_key = public_key.map(|public_key| {
+            assert!(public_key.curve_type() == CurveType::ED25519, "Voucher key must be ed25519");
+            public_key.as_bytes()[1..].to_vec()
+        });
+    }
+
+    pub fn is_voucher_nonce_used(&self, nonce: u64) -> bool {
+        self.used_voucher_nonces.contains(&nonce)
+    }
+
+    // The game's pet count and the voucher are needed to know the amount when tiers or a backend key are set
+    pub fn get_claim_status(&self, account_id: AccountId, pet_count: Option<u64>, voucher: Option<Voucher>) -> ClaimStatus {
+        let claim_amount = self.internal_claim_amount(&account_id, pet_count, voucher.as_ref());
+        let claim = self.claims_by_account.get(&account_id).unwrap_or_default();
+        let next_claim_at = if claim.claim_count == 0 {
+            0
+        } else {
+            claim.last_claim_at + self.cooldown
+        };
+        let remaining_lifetime = if self.lifetime_cap.0 == 0 {
+            None
+        } else {
+            Some(U128(self.lifetime_cap.0.saturating_sub(claim.total_claimed)))
+        };
+
+        ClaimStatus {
+            total_claimed: U128(claim.total_claimed),
+            last_claim_at: claim.last_claim_at,
+            next_claim_at,
+            remaining_lifetime,
+            claim_amount: claim_amount.map(U128),
+            can_claim: self.is_active
+                && self.amount.0 > 0
+                && env::block_timestamp() >= next_claim_at
+                && claim_amount.is_some_and(|amount| {
+                    remaining_lifetime.is_none_or(|remaining| remaining.0 >= amount)
+                        && self.internal_budget_left() >= amount
+                }),
+        }
+    }
+
+    pub fn get_faucet_budget(&self) -> FaucetBudget {
+        let (day_start, claimed_today) = self.internal_current_day();
+
+        FaucetBudget {
+            daily_budget: self.daily_budget,
+            claimed_today: U128(claimed_today),
+            resets_at: day_start + ONE_DAY,
+        }
+    }
+
+    pub fn get_relayers(&self) -> Vec<AccountId> {
+        self.relayers.to_vec()
+    }
+}
+
+impl Faucet {
+    // The start of the current day and the JOY claimed since
+    fn internal_current_day(&self) -> (u64, u128) {
+        let now = env::block_timestamp();
+        if now < self.day_start + ONE_DAY {
+            (self.day_start, self.claimed_today.0)
+        } else {
+            (now - (now - self.day_start) % ONE_DAY, 0)
+        }
+    }
+
+    fn internal_budget_left(&self) -> u128 {
+        if self.daily_budget.0 == 0 {
+            return u128::MAX;
+        }
+        let (_, claimed_today) = self.internal_current_day();
+
+        self.daily_budget.0.saturating_sub(claimed_today)
+    }
+
+    // Check the voucher isn't expired or already used and was signed by the backend
+    fn internal_use_voucher(&mut self, voucher: &Voucher, signature: Base64VecU8) {
+        assert!(voucher.faucet_id == env::current_account_id(), "Voucher is for another faucet");
+        assert!(env::block_timestamp() < voucher.expires_at, "Voucher expired");
+        assert!(!self.used_voucher_nonces.contains(&voucher.nonce), "Voucher already used");
+
+        let public_key = ed25519_dalek::PublicKey::from_bytes(self.voucher_public_key.as_ref().unwrap())
+            .expect("Invalid voucher key");
+        let signature = ed25519_dalek::Signature::try_from(signature.0.as_slice()).expect("Invalid voucher signature");
+        assert!(
+            public_key.verify(&voucher.try_to_vec().unwrap(), &signature).is_ok(),
+            "Invalid voucher signature"
+        );
+
+        self.used_voucher_nonces.insert(&voucher.nonce);
+    }
+
+    fn internal_assert_cooldown(&self, account_id: &AccountId) {
+        let claim: ClaimInfo = self.claims_by_account.get(account_id).unwrap_or_default();
+        if claim.claim_count > 0 {
+            let next_claim_at = claim.last_claim_at + self.cooldown;
+            assert!(env::block_timestamp() >= next_claim_at, "Claim again after {}", next_claim_at);
+        }
+    }
+
+    // The JOY get_joychi would give: the voucher's amount with a backend key set, the tier's amount
+    // with eligibility tiers set, the faucet amount otherwise
+    fn internal_claim_amount(&self, account_id: &AccountId, pet_count: Option<u64>, voucher: Option<&Voucher>) -> Option<u128> {
+        if self.voucher_public_key.is_some() {
+            return voucher
+                .filter(|voucher| {
+                    voucher.faucet_id == env::current_account_id()
+                        && voucher.account_id == *account_id
+                        && env::block_timestamp() < voucher.expires_at
+                        && !self.used_voucher_nonces.contains(&voucher.nonce)
+                })
+                .map(|voucher| voucher.amount.0);
+        }
+
+        if self.game_address.is_some() && !self.eligibility_tiers.is_empty() {
+            return pet_count.and_then(|pet_count| self.internal_eligible_amount(pet_count));
+        }
+
+        Some(self.amount.0)
+    }
+
+    // The JOY of the tier the pet count falls in, None above the last tier
+    pub(crate) fn internal_eligible_amount(&self, pet_count: u64) -> Option<u128> {
+        self.eligibility_tiers
+            .iter()
+            .find(|tier| pet_count <= tier.max_pets)
+            .map(|tier| tier.amount.0)
+            .filter(|amount| *amount > 0)
+    }
+
+    // Count the claim, then register the account on the JOY contract if it isn't yet and transfer
+    pub(crate) fn internal_start_claim(
+        &mut self,
+        account_id: &AccountId,
+        amount: u128,
+        relayer_id: Option<AccountId>,
+        voucher_nonce: Option<u64>,
+    ) -> Promise {
+        let mut claim = self.internal_record_claim(account_id, amount);
+        claim.relayer_id = relayer_id;
+        claim.voucher_nonce = voucher_nonce;
+
+        cross_joychi::ext(self.ft_address.to_owned())
+            .with_static_gas(GAS_FOR_STORAGE_BALANCE)
+            .storage_balance_of(account_id.clone())
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_STORAGE_CHECK)
+                    .resolve_storage_check(claim),
+            )
+    }
+
+    // Check the cooldown, the lifetime cap and the daily budget, then count the claim
+    fn internal_record_claim(&mut self, account_id: &AccountId, amount: u128) -> PendingClaim {
+        let now = env::block_timestamp();
+        let mut claim: ClaimInfo = self.claims_by_account.get(account_id).unwrap_or_default();
+
+        self.internal_assert_cooldown(account_id);
+        if self.lifetime_cap.0 > 0 {
+            assert!(claim.total_claimed + amount <= self.lifetime_cap.0, "Lifetime claim cap reached");
+        }
+        assert!(self.internal_budget_left() >= amount, "Daily faucet budget reached");
+
+        let (day_start, claimed_today) = self.internal_current_day();
+        self.day_start = day_start;
+        self.claimed_today = U128(claimed_today + amount);
+
+        let previous_claim_at = claim.last_claim_at;
+        claim.last_claim_at = now;
+        claim.total_claimed += amount;
+        claim.claim_count += 1;
+        self.claims_by_account.insert(account_id, &claim);
+
+        PendingClaim {
+            account_id: account_id.clone(),
+            amount: U128(amount),
+            relayer_id: None,
+            previous_claim_at,
+            day_start,
+            voucher_nonce: None,
+        }
+    }
+
+    // Give the NEAR for gas and the starter item to an account that claimed for the first time
+    pub(crate) fn internal_first_claim_drips(&mut self, account_id: &AccountId) {
+        if self.near_drip.0 > 0 {
+            Promise::new(account_id.clone()).transfer(self.near_drip.0);
+        }
+
+        if let Some(starter_item) = &self.starter_item {
+            self.starter_items_minted += 1;
+            let metadata = TokenMetadata {
+                title: Some(starter_item.title.clone()),
+                description: None,
+                media: Some(starter_item.media.clone()),
+                media_hash: None,
+                copies: None,
+                issued_at: Some(env::block_timestamp()),
+                expires_at: None,
+                starts_at: None,
+                updated_at: None,
+                extra: None,
+                reference: None,
+                reference_hash: None,
+            };
+
+            cross_item_nft::ext(starter_item.nft_item_address.to_owned())
+                .with_static_gas(GAS_FOR_NFT_MINT)
+                .with_attached_deposit(ATTACHED_DEPOSIT_NFT)
+                .nft_mint(
+                    format!("starter:{}", self.starter_items_minted),
+                    metadata,
+                    account_id.clone(),
+                    starter_item.token_type.clone(),
+                );
+        }
+    }
+
+    // Take back a claim whose JOY couldn't be transferred
+    pub(crate) fn internal_revert_claim(&mut self, pending: &PendingClaim) {
+        let mut claim = self.claims_by_account.get(&pending.account_id).unwrap();
+        claim.last_claim_at = pending.previous_claim_at;
+        claim.total_claimed -= pending.amount.0;
+        claim.claim_count -= 1;
+        self.claims_by_account.insert(&pending.account_id, &claim);
+
+        if self.day_start == pending.day_start {
+            self.claimed_today = U128(self.claimed_today.0.saturating_sub(pending.amount.0));
+        }
+        if let Some(nonce) = pending.voucher_nonce {
+            self.used_voucher_nonces.remove(&nonce);
+        }
+    }
 }
@@ -0,0 +1,46 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedSet};
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId};
+
+use crate::models::contract::{Faucet, FaucetExt, FaucetStorageKey};
+use crate::ONE_DAY;
+
+// The faucet state before claim limits, relayers, vouchers, drips and eligibility tiers were added
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldFaucet {
+    pub owner_id: AccountId,
+    pub ft_address: AccountId,
+    pub is_active: bool,
+    pub amount: U128,
+}
+
+#[near_bindgen]
+impl Faucet {
+    // Migrate the state of a faucet deployed before claim limits, the new settings start as in init
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old_state: OldFaucet = env::state_read().expect("No state to migrate");
+
+        Self {
+            owner_id: old_state.owner_id,
+            ft_address: old_state.ft_address,
+            is_active: old_state.is_active,
+            amount: old_state.amount,
+            cooldown: ONE_DAY,
+            lifetime_cap: U128(0),
+            daily_budget: U128(0),
+            day_start: env::block_timestamp(),
+            claimed_today: U128(0),
+            claims_by_account: LookupMap::new(FaucetStorageKey::ClaimsByAccount.try_to_vec().unwrap()),
+            relayers: UnorderedSet::new(FaucetStorageKey::Relayers.try_to_vec().unwrap()),
+            voucher_public_key: None,
+            used_voucher_nonces: LookupSet::new(FaucetStorageKey::UsedVoucherNonces.try_to_vec().unwrap()),
+            near_drip: U128(0),
+            starter_item: None,
+            starter_items_minted: 0,
+            game_address: None,
+            eligibility_tiers: Vec::new(),
+        }
+    }
+}
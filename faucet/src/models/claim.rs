@@ -0,0 +1,45 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+#[derive(BorshDeserialize, BorshSerialize, Clone, Default)]
+pub struct ClaimInfo {
+    pub last_claim_at: u64,
+    pub total_claimed: u128,
+    pub claim_count: u64,
+}
+
+// When an account can claim next and how much it has left
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ClaimStatus {
+    pub total_claimed: U128,
+    pub last_claim_at: u64,
+    pub next_claim_at: u64,
+    // None when there is no lifetime cap
+    pub remaining_lifetime: Option<U128>,
+    // None when the amount depends on a voucher or a pet count that wasn't given, or the account has no tier
+    pub claim_amount: Option<U128>,
+    pub can_claim: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FaucetBudget {
+    pub daily_budget: U128,
+    pub claimed_today: U128,
+    pub resets_at: u64,
+}
+
+// A claim counted before its JOY is transferred, what is needed to take it back if the transfer fails
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PendingClaim {
+    pub account_id: AccountId,
+    pub amount: U128,
+    pub relayer_id: Option<AccountId>,
+    pub previous_claim_at: u64,
+    pub day_start: u64,
+    pub voucher_nonce: Option<u64>,
+}
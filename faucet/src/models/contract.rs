@@ -1,6 +1,11 @@
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, LookupSet, UnorderedSet};
 use near_sdk::json_types::U128;
-use near_sdk::{near_bindgen, AccountId, PanicOnDefault};
+use near_sdk::{near_bindgen, AccountId, BorshStorageKey, PanicOnDefault};
+
+use super::claim::ClaimInfo;
+use super::drip::StarterItem;
+use super::eligibility::EligibilityTier;
 
 #[near_bindgen]
 #[derive(PanicOnDefault, BorshDeserialize, BorshSerialize)]
@@ -12,5 +17,47 @@ pub struct Faucet {
 
     pub is_active: bool,
     
-    pub amount: U128
+    pub amount: U128,
+
+    /// Time in nanoseconds an account waits between two claims
+    pub cooldown: u64,
+
+    /// JOY an account can claim in total, 0 for no cap
+    pub lifetime_cap: U128,
+
+    /// JOY the faucet can give per day, 0 for no budget
+    pub daily_budget: U128,
+
+    pub day_start: u64,
+
+    pub claimed_today: U128,
+
+    pub claims_by_account: LookupMap<AccountId, ClaimInfo>,
+
+    /// Accounts allowed to claim for another account
+    pub relayers: UnorderedSet<AccountId>,
+
+    /// ed25519 key of the backend, claims need a voucher signed with it when it's set
+    pub voucher_public_key: Option<Vec<u8>>,
+
+    pub used_voucher_nonces: LookupSet<u64>,
+
+    /// NEAR given for gas on the first claim of an account, 0 for none
+    pub near_drip: U128,
+
+    pub starter_item: Option<StarterItem>,
+
+    pub starter_items_minted: u64,
+
+    /// joy_v1 contract read for the pets of the claimer, None to skip the eligibility check
+    pub game_address: Option<AccountId>,
+
+    pub eligibility_tiers: Vec<EligibilityTier>,
+}
+
+#[derive(BorshSerialize, BorshStorageKey)]
+pub enum FaucetStorageKey {
+    ClaimsByAccount,
+    Relayers,
+    UsedVoucherNonces,
 }
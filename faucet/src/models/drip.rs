@@ -0,0 +1,25 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+use super::nft_request::external::TokenType;
+
+// The item NFT minted to an account on its first claim, the faucet must be a minter of nft_item
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StarterItem {
+    pub nft_item_address: AccountId,
+    pub title: String,
+    pub media: String,
+    pub token_type: Option<TokenType>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FaucetInfo {
+    pub is_active: bool,
+    pub amount: U128,
+    pub near_drip: U128,
+    pub starter_item: Option<StarterItem>,
+}
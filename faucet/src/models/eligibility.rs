@@ -0,0 +1,19 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+// Accounts owning up to max_pets pets get amount, the tier with the lowest max_pets that fits is used
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EligibilityTier {
+    pub max_pets: u64,
+    pub amount: U128,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FaucetEligibility {
+    pub game_address: Option<AccountId>,
+    pub tiers: Vec<EligibilityTier>,
+}
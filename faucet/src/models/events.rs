@@ -0,0 +1,88 @@
+use std::fmt;
+
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+
+pub const FAUCET_STANDARD_NAME: &str = "joychi_faucet";
+pub const FAUCET_EVENT_VERSION: &str = "1.0.0";
+
+/// The events of the faucet, a claim is logged once its JOY was transferred or failed
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "event", content = "data")]
+#[serde(rename_all = "snake_case")]
+#[serde(crate = "near_sdk::serde")]
+#[non_exhaustive]
+pub enum EventLogVariant {
+    FaucetClaim(Vec<FaucetClaimLog>),
+    FaucetClaimFailed(Vec<FaucetClaimLog>),
+    FaucetClaimIneligible(Vec<FaucetClaimIneligibleLog>),
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct EventLog {
+    pub standard: String,
+    pub version: String,
+
+    #[serde(flatten)]
+    pub event: EventLogVariant,
+}
+
+impl EventLog {
+    pub(crate) fn faucet(event: EventLogVariant) -> Self {
+        Self {
+            standard: FAUCET_STANDARD_NAME.to_string(),
+            version: FAUCET_EVENT_VERSION.to_string(),
+            event,
+        }
+    }
+
+    pub(crate) fn emit(&self) {
+        near_sdk::env::log_str(&self.to_string());
+    }
+}
+
+impl fmt::Display for EventLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!(
+            "EVENT_JSON:{}",
+            &near_sdk::serde_json::to_string(self).map_err(|_| fmt::Error)?
+        ))
+    }
+}
+
+/// A faucet claim
+///
+/// Arguments
+/// * `account_id`: account that got the JOY
+/// * `amount`: JOY claimed
+/// * `relayer_id`: relayer that claimed for the account, if any
+/// * `registered`: whether the faucet paid the storage of the account on the JOY contract
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FaucetClaimLog {
+    pub account_id: String,
+    pub amount: U128,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relayer_id: Option<String>,
+
+    pub registered: bool,
+}
+
+/// A claim refused from the game state of the account
+///
+/// Arguments
+/// * `account_id`: account that asked for the JOY
+/// * `pet_count`: pets owned by the account, None if the game couldn't be read
+/// * `reason`: why the account isn't eligible
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FaucetClaimIneligibleLog {
+    pub account_id: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pet_count: Option<u64>,
+
+    pub reason: String,
+}
@@ -1,7 +1,16 @@
+use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::{ext_contract, json_types::U128, AccountId};
 
 #[ext_contract(cross_joychi)]
 pub trait CrossCall {
     fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128);
     fn storage_deposit(&mut self, account_id: AccountId);
+    fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance>;
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct StorageBalance {
+    pub total: U128,
+    pub available: U128,
 }
@@ -0,0 +1,14 @@
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{ext_contract, AccountId};
+
+#[ext_contract(cross_joychi_game)]
+pub trait GameCrossCall {
+    fn get_pet_owner_stats(&self, account_id: AccountId) -> PetOwnerStats;
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PetOwnerStats {
+    pub pet_count: u64,
+    pub pets_created: u64,
+}
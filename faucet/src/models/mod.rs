@@ -1,2 +1,9 @@
+pub mod claim;
 pub mod contract;
+pub mod drip;
+pub mod eligibility;
+pub mod events;
 pub mod ft_request;
+pub mod game_request;
+pub mod nft_request;
+pub mod voucher;
@@ -0,0 +1,43 @@
+use near_sdk::json_types::Base64VecU8;
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+};
+use near_sdk::{ext_contract, AccountId};
+
+#[ext_contract(cross_item_nft)]
+pub trait ItemCrossCall {
+    fn nft_mint(
+        &mut self,
+        token_id: String,
+        metadata: TokenMetadata,
+        receiver_id: AccountId,
+        token_type: Option<TokenType>,
+    );
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TokenType {
+    Tool,
+    Consumable,
+    Cosmetic,
+    EvolutionItem,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenMetadata {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub media: Option<String>,
+    pub media_hash: Option<Base64VecU8>,
+    pub copies: Option<u64>,
+    pub issued_at: Option<u64>,
+    pub expires_at: Option<u64>,
+    pub starts_at: Option<u64>,
+    pub updated_at: Option<u64>,
+    pub extra: Option<String>,
+    pub reference: Option<String>,
+    pub reference_hash: Option<Base64VecU8>,
+}
@@ -0,0 +1,18 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::AccountId;
+
+// Issued by the backend once a player passed its checks, for one faucet so it can't be replayed on another deployment.
+// The signature is made over the borsh serialization of the voucher, the fields in order:
+// faucet_id and account_id as a u32 little endian length then the UTF-8 bytes,
+// amount as a u128 little endian, nonce and expires_at as u64 little endian
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Voucher {
+    pub faucet_id: AccountId,
+    pub account_id: AccountId,
+    pub amount: U128,
+    pub nonce: u64,
+    pub expires_at: u64,
+}
@@ -3,13 +3,17 @@ use near_token::NearToken;
 use near_units::parse_near;
 use serde_json::json;
 mod helpers;
-use near_sdk::json_types::U128;
+use ed25519_dalek::Signer;
+use near_sdk::borsh::{self, BorshSerialize};
+use near_sdk::json_types::{Base64VecU8, U128};
 use near_workspaces::{Account, Contract};
 
 use helpers::storage_deposit;
 
-const JOY_TOKEN_WASM_FILEPATH: &str = "../res/ft_token.wasm";
-const JOY_FAUCET_WASM_FILEPATH: &str = "../res/faucet.wasm";
+const JOY_TOKEN_PROJECT_PATH: &str = "../ft_token";
+const JOY_FAUCET_PROJECT_PATH: &str = "../faucet";
+const NFT_ITEM_PROJECT_PATH: &str = "../nft_item";
+const JOYCHI_PROJECT_PATH: &str = "../joy_v1";
 
 const ALICE_NEAR: NearToken = NearToken::from_near(30);
 const BOB_NEAR: NearToken = NearToken::from_near(30);
@@ -23,10 +27,14 @@ async fn main() -> anyhow::Result<()> {
     let worker = near_workspaces::sandbox().await?;
 
     // deploy contracts
-    let ft_wasm = std::fs::read(JOY_TOKEN_WASM_FILEPATH)?;
+    let ft_wasm = near_workspaces::compile_project(JOY_TOKEN_PROJECT_PATH).await?;
     let ft_contract = worker.dev_deploy(&ft_wasm).await?;
-    let faucet_wasm = std::fs::read(JOY_FAUCET_WASM_FILEPATH)?;
+    let faucet_wasm = near_workspaces::compile_project(JOY_FAUCET_PROJECT_PATH).await?;
     let faucet_contract = worker.dev_deploy(&faucet_wasm).await?;
+    let nft_item_wasm = near_workspaces::compile_project(NFT_ITEM_PROJECT_PATH).await?;
+    let nft_item_contract = worker.dev_deploy(&nft_item_wasm).await?;
+    let joychi_wasm = near_workspaces::compile_project(JOYCHI_PROJECT_PATH).await?;
+    let joychi_contract = worker.dev_deploy(&joychi_wasm).await?;
 
     let owner = worker.root_account().unwrap();
 
@@ -45,6 +53,30 @@ async fn main() -> anyhow::Result<()> {
         .await?
         .into_result()?;
 
+    // Create Carol Account, not registered on the JOY contract
+    let carol = owner
+        .create_subaccount("carol")
+        .initial_balance(BOB_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Create Dave Account, claims for the first time with the drips on
+    let dave = owner
+        .create_subaccount("dave")
+        .initial_balance(BOB_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Create Eve Account, claims with the eligibility tiers on
+    let eve = owner
+        .create_subaccount("eve")
+        .initial_balance(BOB_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
     // Call new construct for fungible token
     ft_contract
         .call("new_default_meta")
@@ -66,14 +98,60 @@ async fn main() -> anyhow::Result<()> {
         .await?
         .into_result()?;
 
+    // The faucet mints the starter items
+    nft_item_contract
+        .call("new_default_meta")
+        .args_json(json!({
+            "owner_id": owner.id()
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner
+        .call(nft_item_contract.id(), "add_authorized_minter")
+        .args_json(json!({
+            "account_id": faucet_contract.id()
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // The game the faucet reads the pets from
+    joychi_contract
+        .call("init")
+        .args_json(json!({
+            "nft_addr": nft_item_contract.id(),
+            "nft_item_addr": nft_item_contract.id(),
+            "ft_addr": ft_contract.id()
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
     test_total_supply(&owner, &ft_contract).await?;
     test_faucet_token(&owner, &alice, &faucet_contract, &ft_contract).await?;
+    test_claim_limits(&alice, &bob, &faucet_contract, &ft_contract).await?;
+    test_voucher_claims(&alice, &faucet_contract, &ft_contract).await?;
+    test_claim_callbacks(&alice, &carol, &faucet_contract, &ft_contract).await?;
+    test_faucet_admin(&owner, &dave, &faucet_contract, &ft_contract, &nft_item_contract).await?;
+    test_faucet_eligibility(&eve, &faucet_contract, &ft_contract, &joychi_contract).await?;
 
     // Check faucet token JOY
 
     Ok(())
 }
 
+// The voucher the backend signs, same layout as the faucet one
+#[derive(BorshSerialize)]
+struct Voucher {
+    faucet_id: near_sdk::AccountId,
+    account_id: near_sdk::AccountId,
+    amount: U128,
+    nonce: u64,
+    expires_at: u64,
+}
+
 async fn test_total_supply(owner: &Account, contract: &Contract) -> anyhow::Result<()> {
     let initial_balance = U128::from(parse_near!("1,000,000,000 N"));
     let res: U128 = owner
@@ -131,7 +209,15 @@ async fn test_faucet_token(
         .await?
         .into_result()?;
 
-    // Faucet
+    // Faucet, the owner relays the claim of the user
+
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "add_relayer")
+        .args_json(json!({"account_id": owner.id()}))
+        .transact()
+        .await?
+        .into_result()?;
 
     owner
         .call(faucet_contract.id(), "get_joychi")
@@ -157,3 +243,552 @@ async fn test_faucet_token(
     println!("      Passed ✅ test_faucet");
     Ok(())
 }
+
+async fn test_claim_limits(
+    alice: &Account,
+    bob: &Account,
+    faucet_contract: &Contract,
+    ft_contract: &Contract,
+) -> anyhow::Result<()> {
+    // Alice claimed through the relayer, she has to wait a day before claiming again
+    let status: serde_json::Value = alice
+        .call(faucet_contract.id(), "get_claim_status")
+        .args_json(json!({"account_id": alice.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(status["total_claimed"], parse_near!("2 N").to_string());
+    assert_eq!(status["can_claim"], false);
+
+    let result = alice
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.is_failure());
+
+    // Only relayers can claim for another account
+    let result = alice
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({"addr_to": bob.id()}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.is_failure());
+
+    // No cooldown, 2 JOY per account and 4 JOY per day
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_claim_limits")
+        .args_json(json!({"cooldown": 0, "lifetime_cap": U128(parse_near!("2 N")), "daily_budget": U128(parse_near!("4 N"))}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    bob.call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let bob_balance: U128 = bob
+        .call(ft_contract.id(), "ft_balance_of")
+        .args_json(json!({"account_id": bob.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(bob_balance, U128::from(parse_near!("2 N")));
+
+    // Bob reached his lifetime cap
+    let result = bob
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.is_failure());
+
+    // The 4 JOY of the day are given
+    let budget: serde_json::Value = alice
+        .call(faucet_contract.id(), "get_faucet_budget")
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(budget["claimed_today"], parse_near!("4 N").to_string());
+
+    println!("      Passed ✅ test_claim_limits");
+    Ok(())
+}
+
+async fn test_voucher_claims(
+    user: &Account,
+    faucet_contract: &Contract,
+    ft_contract: &Contract,
+) -> anyhow::Result<()> {
+    let secret = ed25519_dalek::SecretKey::from_bytes(&[7; 32])?;
+    let public = ed25519_dalek::PublicKey::from(&secret);
+    let backend = ed25519_dalek::Keypair { secret, public };
+
+    let mut key_bytes = vec![0];
+    key_bytes.extend_from_slice(backend.public.as_bytes());
+    let public_key = near_sdk::PublicKey::try_from(key_bytes)?;
+
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_claim_limits")
+        .args_json(json!({"cooldown": 0, "lifetime_cap": U128(0), "daily_budget": U128(0)}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_voucher_public_key")
+        .args_json(json!({"public_key": public_key}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Claims need a voucher now
+    let result = user
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.is_failure());
+
+    let voucher = Voucher {
+        faucet_id: faucet_contract.id().as_str().parse()?,
+        account_id: user.id().as_str().parse()?,
+        amount: U128(parse_near!("5 N")),
+        nonce: 1,
+        expires_at: u64::MAX,
+    };
+    let signature = backend.sign(&voucher.try_to_vec()?);
+    let args = json!({
+        "voucher": {
+            "faucet_id": faucet_contract.id(),
+            "account_id": user.id(),
+            "amount": voucher.amount,
+            "nonce": voucher.nonce,
+            "expires_at": voucher.expires_at
+        },
+        "signature": Base64VecU8(signature.to_bytes().to_vec())
+    });
+
+    // A voucher signed with another key is refused
+    let other = ed25519_dalek::Keypair {
+        secret: ed25519_dalek::SecretKey::from_bytes(&[8; 32])?,
+        public: ed25519_dalek::PublicKey::from(&ed25519_dalek::SecretKey::from_bytes(&[8; 32])?),
+    };
+    let mut forged_args = args.clone();
+    forged_args["signature"] = json!(Base64VecU8(other.sign(&voucher.try_to_vec()?).to_bytes().to_vec()));
+    let result = user
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(forged_args)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.is_failure());
+
+    // A voucher signed for another faucet is refused
+    let other_faucet = Voucher {
+        faucet_id: "other-faucet.test.near".parse()?,
+        ..voucher
+    };
+    let mut replayed_args = args.clone();
+    replayed_args["voucher"]["faucet_id"] = json!(other_faucet.faucet_id);
+    replayed_args["signature"] = json!(Base64VecU8(backend.sign(&other_faucet.try_to_vec()?).to_bytes().to_vec()));
+    let result = user
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(replayed_args)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.is_failure());
+
+    let balance_before: U128 = user
+        .call(ft_contract.id(), "ft_balance_of")
+        .args_json(json!({"account_id": user.id()}))
+        .view()
+        .await?
+        .json()?;
+
+    user.call(faucet_contract.id(), "get_joychi")
+        .args_json(args.clone())
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let balance_after: U128 = user
+        .call(ft_contract.id(), "ft_balance_of")
+        .args_json(json!({"account_id": user.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(balance_after.0, balance_before.0 + parse_near!("5 N"));
+
+    // The voucher can't be used twice
+    let result = user
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(args)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.is_failure());
+
+    println!("      Passed ✅ test_voucher_claims");
+    Ok(())
+}
+
+async fn test_claim_callbacks(
+    alice: &Account,
+    carol: &Account,
+    faucet_contract: &Contract,
+    ft_contract: &Contract,
+) -> anyhow::Result<()> {
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_voucher_public_key")
+        .args_json(json!({"public_key": null}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Alice is already registered, the faucet only transfers the JOY
+    let result = alice
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.logs().iter().any(|log| log.contains("\"event\":\"faucet_claim\"") && log.contains("\"registered\":false")));
+    assert!(result.json::<bool>()?);
+
+    // Carol is registered by the faucet first
+    let result = carol
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.logs().iter().any(|log| log.contains("\"registered\":true")));
+    assert!(result.json::<bool>()?);
+
+    let carol_balance: U128 = carol
+        .call(ft_contract.id(), "ft_balance_of")
+        .args_json(json!({"account_id": carol.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(carol_balance, U128::from(parse_near!("2 N")));
+
+    // The faucet doesn't hold that much JOY, the failed claim isn't counted
+    let status_before: serde_json::Value = alice
+        .call(faucet_contract.id(), "get_claim_status")
+        .args_json(json!({"account_id": alice.id()}))
+        .view()
+        .await?
+        .json()?;
+
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_faucet_amount")
+        .args_json(json!({"amount": U128::from(parse_near!("100,000 N"))}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let result = alice
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.logs().iter().any(|log| log.contains("\"event\":\"faucet_claim_failed\"")));
+    assert!(!result.json::<bool>()?);
+
+    let status_after: serde_json::Value = alice
+        .call(faucet_contract.id(), "get_claim_status")
+        .args_json(json!({"account_id": alice.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(status_after["total_claimed"], status_before["total_claimed"]);
+    assert_eq!(status_after["last_claim_at"], status_before["last_claim_at"]);
+
+    println!("      Passed ✅ test_claim_callbacks");
+    Ok(())
+}
+
+async fn test_faucet_admin(
+    owner: &Account,
+    dave: &Account,
+    faucet_contract: &Contract,
+    ft_contract: &Contract,
+    nft_item_contract: &Contract,
+) -> anyhow::Result<()> {
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_faucet_amount")
+        .args_json(json!({"amount": U128::from(parse_near!("2 N"))}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Paused faucet
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_active")
+        .args_json(json!({"is_active": false}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let result = dave
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.is_failure());
+
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_active")
+        .args_json(json!({"is_active": true}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Fund the faucet with ft_transfer_call
+    let balance_before: U128 = owner
+        .call(ft_contract.id(), "ft_balance_of")
+        .args_json(json!({"account_id": faucet_contract.id()}))
+        .view()
+        .await?
+        .json()?;
+
+    owner
+        .call(ft_contract.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": faucet_contract.id(),
+            "amount": U128::from(parse_near!("100 N")),
+            "msg": "",
+        }))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let balance_after: U128 = owner
+        .call(ft_contract.id(), "ft_balance_of")
+        .args_json(json!({"account_id": faucet_contract.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(balance_after.0, balance_before.0 + parse_near!("100 N"));
+
+    // Withdraw part of it back to the owner
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "withdraw_joy")
+        .args_json(json!({"receiver_id": owner.id(), "amount": U128::from(parse_near!("50 N"))}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let balance_withdrawn: U128 = owner
+        .call(ft_contract.id(), "ft_balance_of")
+        .args_json(json!({"account_id": faucet_contract.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(balance_withdrawn.0, balance_after.0 - parse_near!("50 N"));
+
+    // NEAR and a starter item on the first claim
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_drips")
+        .args_json(json!({
+            "near_drip": U128::from(parse_near!("1 N")),
+            "starter_item": {
+                "nft_item_address": nft_item_contract.id(),
+                "title": "Starter Pickaxe",
+                "media": "https://joychi.io/starter.png",
+                "token_type": "Tool",
+            },
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let faucet_info: serde_json::Value = dave
+        .call(faucet_contract.id(), "get_faucet_info")
+        .args_json(json!({}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(faucet_info["is_active"], true);
+    assert_eq!(faucet_info["near_drip"], parse_near!("1 N").to_string());
+
+    let near_before = dave.view_account().await?.balance;
+
+    let result = dave
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.json::<bool>()?);
+
+    // The claim costs some gas but the drip is bigger
+    let near_after = dave.view_account().await?.balance;
+    assert!(near_after > near_before);
+
+    let starter_item: serde_json::Value = dave
+        .call(nft_item_contract.id(), "nft_token")
+        .args_json(json!({"token_id": "starter:1"}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(starter_item["owner_id"], dave.id().to_string());
+
+    println!("      Passed ✅ test_faucet_admin");
+    Ok(())
+}
+
+async fn test_faucet_eligibility(
+    eve: &Account,
+    faucet_contract: &Contract,
+    ft_contract: &Contract,
+    joychi_contract: &Contract,
+) -> anyhow::Result<()> {
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_drips")
+        .args_json(json!({"near_drip": U128(0), "starter_item": null}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // No drip for accounts without pets, the claim is refused in the callback
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_eligibility")
+        .args_json(json!({
+            "game_address": joychi_contract.id(),
+            "tiers": [{"max_pets": 0, "amount": U128(0)}],
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let result = eve
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result
+        .logs()
+        .iter()
+        .any(|log| log.contains("\"event\":\"faucet_claim_ineligible\"") && log.contains("\"pet_count\":0")));
+    assert!(!result.json::<bool>()?);
+
+    // A larger drip for accounts without pets, none above 3 pets
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_eligibility")
+        .args_json(json!({
+            "game_address": joychi_contract.id(),
+            "tiers": [
+                {"max_pets": 3, "amount": U128::from(parse_near!("2 N"))},
+                {"max_pets": 0, "amount": U128::from(parse_near!("5 N"))},
+            ],
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let eligibility: serde_json::Value = eve
+        .call(faucet_contract.id(), "get_eligibility")
+        .args_json(json!({}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(eligibility["tiers"][0]["max_pets"], 0);
+
+    // The claim status gives the amount of the tier the pet count falls in
+    let status: serde_json::Value = eve
+        .call(faucet_contract.id(), "get_claim_status")
+        .args_json(json!({"account_id": eve.id(), "pet_count": 0}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(status["claim_amount"], parse_near!("5 N").to_string());
+    assert_eq!(status["can_claim"], true);
+
+    let status: serde_json::Value = eve
+        .call(faucet_contract.id(), "get_claim_status")
+        .args_json(json!({"account_id": eve.id(), "pet_count": 4}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(status["claim_amount"], serde_json::Value::Null);
+    assert_eq!(status["can_claim"], false);
+
+    let result = eve
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.json::<bool>()?);
+
+    let eve_balance: U128 = eve
+        .call(ft_contract.id(), "ft_balance_of")
+        .args_json(json!({"account_id": eve.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(eve_balance, U128::from(parse_near!("5 N")));
+
+    // The game can't be read, the claim is refused
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_claim_limits")
+        .args_json(json!({"cooldown": 0, "lifetime_cap": U128(0), "daily_budget": U128(0)}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    faucet_contract
+        .as_account()
+        .call(faucet_contract.id(), "set_eligibility")
+        .args_json(json!({
+            "game_address": ft_contract.id(),
+            "tiers": [{"max_pets": 0, "amount": U128::from(parse_near!("5 N"))}],
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let result = eve
+        .call(faucet_contract.id(), "get_joychi")
+        .args_json(json!({}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.logs().iter().any(|log| log.contains("Game state unavailable")));
+    assert!(!result.json::<bool>()?);
+
+    println!("      Passed ✅ test_faucet_eligibility");
+    Ok(())
+}
@@ -1,5 +1,5 @@
 
-use near_sdk::json_types::Base64VecU8;
+use near_sdk::json_types::{Base64VecU8, U128};
 use near_sdk::AccountId;
 use std::collections::HashMap;
 use near_token::NearToken;
@@ -115,8 +115,20 @@ pub struct ItemMetadata {
     pub prototype_item_rarity: ItemRarity,
     pub prototype_itemmining_power: u128,
     pub prototype_itemmining_charge_time: u128,
-    pub owner: AccountId,
+    pub prototype_item_max_durability: u128,
+    pub prototype_item_repair_price: u128,
+    pub total_supply: u64,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ItemInstance {
+    pub token_id: String,
+    pub item_id: ItemId,
+    pub serial: u64,
     pub is_lock: bool,
+    pub durability: u128,
+    pub minted_at: u64,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -145,7 +157,10 @@ pub struct MiningData {
     pub total_mining_power: u128,
     pub total_mining_charge_time: u128,
     pub last_mining_time: u128,
-    pub mining_tool_used: Vec<u128>,
+    pub mining_tool_used: Vec<String>,
+    pub season_id: u64,
+    pub season_points: u128,
+    pub settled_season_id: u64,
 }
 
 
@@ -168,6 +183,19 @@ pub async fn storage_deposit(
     Ok(())
 }
 
+pub async fn ft_balance_of(
+    ft_contract: &Contract,
+    account_id: &near_workspaces::AccountId,
+) -> anyhow::Result<u128> {
+    let balance: U128 = ft_contract
+        .call("ft_balance_of")
+        .args_json(json!({"account_id": account_id}))
+        .view()
+        .await?
+        .json()?;
+    Ok(balance.0)
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(crate = "near_sdk::serde")]
 pub struct JsonToken {
@@ -181,6 +209,14 @@ pub struct JsonToken {
     pub approved_account_ids: HashMap<AccountId, u64>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenLock {
+    pub locked_by: AccountId,
+    pub reason: String,
+    pub locked_at: u64,
+}
+
 pub async fn get_pet_metadata_by_id(
     user: &Account,
     pet_id: PetId,
@@ -232,6 +268,23 @@ pub async fn get_item_prototype_metadata_by_id(
     Ok(item)
 }
 
+pub async fn get_item_instance_by_token_id(
+    user: &Account,
+    token_id: &str,
+    joychi_contract: &Contract,
+) -> anyhow::Result<ItemInstance> {
+    let item_instance: ItemInstance = user
+        .call(joychi_contract.id(), "get_item_instance_by_token_id")
+        .args_json(json!({
+            "token_id": token_id
+        }))
+        .transact()
+        .await?
+        .json()?;
+
+    Ok(item_instance)
+}
+
 pub async fn get_score_pet_by_id(
     user: &Account,
     pet_id: PetId,
@@ -267,4 +320,21 @@ pub async fn get_mining_data_by_id(
         .json()?;
 
     Ok(pet)
-}
\ No newline at end of file
+}
+
+pub async fn get_nft_lock_info(
+    user: &Account,
+    token_id: &str,
+    nft_contract: &Contract,
+) -> anyhow::Result<Option<TokenLock>> {
+    let token_lock: Option<TokenLock> = user
+        .call(nft_contract.id(), "nft_lock_info")
+        .args_json(json!({
+            "token_id": token_id
+        }))
+        .transact()
+        .await?
+        .json()?;
+
+    Ok(token_lock)
+}
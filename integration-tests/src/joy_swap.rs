@@ -0,0 +1,277 @@
+use near_gas::NearGas;
+use near_token::NearToken;
+use near_units::parse_near;
+use serde_json::json;
+mod helpers;
+use near_sdk::json_types::U128;
+use near_workspaces::{Account, Contract};
+
+use helpers::{ft_balance_of, storage_deposit};
+
+const JOY_TOKEN_PROJECT_PATH: &str = "../ft_token";
+const JOY_SWAP_PROJECT_PATH: &str = "../joy_swap";
+
+const INITIAL_NEAR: NearToken = NearToken::from_near(30);
+
+const DEFAULT_DEPOSIT: NearToken = NearToken::from_yoctonear(1);
+const DEFAULT_GAS: NearGas = NearGas::from_tgas(300);
+
+// 0.3% swap fee
+const FEE_BASIS_POINTS: u32 = 30;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // initiate environemnt
+    let worker = near_workspaces::sandbox().await?;
+
+    // deploy contracts, a second fungible token stands for wNEAR
+    let ft_wasm = near_workspaces::compile_project(JOY_TOKEN_PROJECT_PATH).await?;
+    let joy_contract = worker.dev_deploy(&ft_wasm).await?;
+    let wnear_contract = worker.dev_deploy(&ft_wasm).await?;
+    let swap_wasm = near_workspaces::compile_project(JOY_SWAP_PROJECT_PATH).await?;
+    let swap_contract = worker.dev_deploy(&swap_wasm).await?;
+
+    let owner = worker.root_account().unwrap();
+
+    // Create Alice Account, the liquidity provider
+    let alice = owner
+        .create_subaccount("alice")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+    // Create Bob Account, swaps wNEAR for JOY
+    let bob = owner
+        .create_subaccount("bob")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    for token in [&joy_contract, &wnear_contract] {
+        token
+            .call("new_default_meta")
+            .args_json(json!({
+                "owner_id": owner.id(),
+                "total_supply": U128::from(parse_near!("1,000,000,000 N")),
+            }))
+            .transact()
+            .await?
+            .into_result()?;
+
+        for account in [swap_contract.as_account(), &alice, &bob] {
+            storage_deposit(&owner, token, account).await?;
+        }
+    }
+
+    swap_contract
+        .call("new")
+        .args_json(json!({
+            "owner_id": owner.id(),
+            "joy_token_id": joy_contract.id(),
+            "wnear_token_id": wnear_contract.id(),
+            "fee_bps": FEE_BASIS_POINTS,
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    ft_transfer(&owner, &joy_contract, &alice, parse_near!("1000 N")).await?;
+    ft_transfer(&owner, &wnear_contract, &alice, parse_near!("100 N")).await?;
+    ft_transfer(&owner, &wnear_contract, &bob, parse_near!("10 N")).await?;
+
+    test_add_liquidity(&alice, &swap_contract, &joy_contract, &wnear_contract).await?;
+    test_swap(&bob, &swap_contract, &joy_contract, &wnear_contract).await?;
+    test_swap_slippage(&bob, &swap_contract, &wnear_contract).await?;
+    test_remove_liquidity(&alice, &swap_contract, &joy_contract).await?;
+
+    Ok(())
+}
+
+async fn ft_transfer(owner: &Account, token: &Contract, receiver: &Account, amount: u128) -> anyhow::Result<()> {
+    owner
+        .call(token.id(), "ft_transfer")
+        .args_json(json!({"receiver_id": receiver.id(), "amount": U128(amount)}))
+        .deposit(DEFAULT_DEPOSIT)
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+async fn test_add_liquidity(
+    alice: &Account,
+    swap_contract: &Contract,
+    joy_contract: &Contract,
+    wnear_contract: &Contract,
+) -> anyhow::Result<()> {
+    alice
+        .call(swap_contract.id(), "storage_deposit")
+        .args_json(json!({}))
+        .deposit(NearToken::from_millinear(10))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Deposit both tokens with an empty msg
+    for (token, amount) in [(joy_contract, parse_near!("1000 N")), (wnear_contract, parse_near!("100 N"))] {
+        alice
+            .call(token.id(), "ft_transfer_call")
+            .args_json(json!({"receiver_id": swap_contract.id(), "amount": U128(amount), "msg": ""}))
+            .deposit(DEFAULT_DEPOSIT)
+            .gas(DEFAULT_GAS)
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    let shares: U128 = alice
+        .call(swap_contract.id(), "add_liquidity")
+        .args_json(json!({
+            "joy_amount": U128::from(parse_near!("1000 N")),
+            "wnear_amount": U128::from(parse_near!("100 N")),
+            "min_shares": U128(1),
+        }))
+        .transact()
+        .await?
+        .json()?;
+    assert!(shares.0 > 0);
+
+    let pool: serde_json::Value = alice
+        .call(swap_contract.id(), "get_pool")
+        .args_json(json!({}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(pool["reserve_joy"], parse_near!("1000 N").to_string());
+    assert_eq!(pool["reserve_wnear"], parse_near!("100 N").to_string());
+    assert_eq!(pool["total_shares"], shares.0.to_string());
+
+    println!("      Passed ✅ test_add_liquidity");
+    Ok(())
+}
+
+async fn test_swap(
+    bob: &Account,
+    swap_contract: &Contract,
+    joy_contract: &Contract,
+    wnear_contract: &Contract,
+) -> anyhow::Result<()> {
+    let expected: U128 = bob
+        .call(swap_contract.id(), "get_return")
+        .args_json(json!({"token_in": wnear_contract.id(), "amount_in": U128::from(parse_near!("1 N"))}))
+        .view()
+        .await?
+        .json()?;
+    // Less than 10 JOY for 1 wNEAR because of the fee and the price impact
+    assert!(expected.0 > 0 && expected.0 < parse_near!("10 N"));
+
+    // Bob isn't registered, his deposit and his swap are refunded
+    for msg in [String::new(), json!({"min_amount_out": expected}).to_string()] {
+        bob.call(wnear_contract.id(), "ft_transfer_call")
+            .args_json(json!({"receiver_id": swap_contract.id(), "amount": U128::from(parse_near!("1 N")), "msg": msg}))
+            .deposit(DEFAULT_DEPOSIT)
+            .gas(DEFAULT_GAS)
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    assert_eq!(ft_balance_of(wnear_contract, bob.id()).await?, parse_near!("10 N"));
+    assert_eq!(ft_balance_of(joy_contract, bob.id()).await?, 0);
+    let deposit: serde_json::Value = bob
+        .call(swap_contract.id(), "get_deposit")
+        .args_json(json!({"account_id": bob.id()}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(deposit["wnear"], "0");
+
+    // Once registered, the swap goes through
+    bob.call(swap_contract.id(), "storage_deposit")
+        .args_json(json!({}))
+        .deposit(NearToken::from_millinear(10))
+        .transact()
+        .await?
+        .into_result()?;
+
+    bob.call(wnear_contract.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": swap_contract.id(),
+            "amount": U128::from(parse_near!("1 N")),
+            "msg": json!({"min_amount_out": expected}).to_string(),
+        }))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    assert_eq!(ft_balance_of(joy_contract, bob.id()).await?, expected.0);
+    assert_eq!(ft_balance_of(wnear_contract, bob.id()).await?, parse_near!("9 N"));
+
+    println!("      Passed ✅ test_swap");
+    Ok(())
+}
+
+async fn test_swap_slippage(bob: &Account, swap_contract: &Contract, wnear_contract: &Contract) -> anyhow::Result<()> {
+    let expected: U128 = bob
+        .call(swap_contract.id(), "get_return")
+        .args_json(json!({"token_in": wnear_contract.id(), "amount_in": U128::from(parse_near!("1 N"))}))
+        .view()
+        .await?
+        .json()?;
+
+    // The swap fails and the wNEAR goes back to bob
+    bob.call(wnear_contract.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": swap_contract.id(),
+            "amount": U128::from(parse_near!("1 N")),
+            "msg": json!({"min_amount_out": U128(expected.0 + 1)}).to_string(),
+        }))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    assert_eq!(ft_balance_of(wnear_contract, bob.id()).await?, parse_near!("9 N"));
+
+    println!("      Passed ✅ test_swap_slippage");
+    Ok(())
+}
+
+async fn test_remove_liquidity(alice: &Account, swap_contract: &Contract, joy_contract: &Contract) -> anyhow::Result<()> {
+    let shares: U128 = alice
+        .call(swap_contract.id(), "get_shares")
+        .args_json(json!({"account_id": alice.id()}))
+        .view()
+        .await?
+        .json()?;
+
+    let removed: serde_json::Value = alice
+        .call(swap_contract.id(), "remove_liquidity")
+        .args_json(json!({"shares": shares, "min_joy_amount": U128(0), "min_wnear_amount": U128(0)}))
+        .transact()
+        .await?
+        .json()?;
+    // The fee stays in the pool for the liquidity provider
+    let joy_amount: u128 = removed["joy"].as_str().unwrap().parse()?;
+    assert!(joy_amount < parse_near!("1000 N"));
+    let wnear_amount: u128 = removed["wnear"].as_str().unwrap().parse()?;
+    assert_eq!(wnear_amount, parse_near!("101 N"));
+
+    alice
+        .call(swap_contract.id(), "withdraw")
+        .args_json(json!({"token_id": joy_contract.id(), "amount": U128(joy_amount)}))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    assert_eq!(ft_balance_of(joy_contract, alice.id()).await?, joy_amount);
+
+    println!("      Passed ✅ test_remove_liquidity");
+    Ok(())
+}
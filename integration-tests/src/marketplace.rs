@@ -0,0 +1,529 @@
+use near_gas::NearGas;
+use near_token::NearToken;
+use near_units::parse_near;
+use serde_json::json;
+mod helpers;
+use near_sdk::json_types::U128;
+use near_workspaces::{network::Sandbox, Account, Contract, Worker};
+
+use helpers::{ft_balance_of, storage_deposit, JsonToken};
+
+const JOY_TOKEN_PROJECT_PATH: &str = "../ft_token";
+const NFT_PET_PROJECT_PATH: &str = "../nft_pet";
+const MARKETPLACE_PROJECT_PATH: &str = "../marketplace";
+
+const INITIAL_NEAR: NearToken = NearToken::from_near(30);
+
+const DEFAULT_DEPOSIT: NearToken = NearToken::from_yoctonear(1);
+const DEFAULT_GAS: NearGas = NearGas::from_tgas(300);
+
+const SALE_PRICE: u128 = 1_000;
+// 5% royalty paid to the game treasury
+const ROYALTY_BASIS_POINTS: u32 = 500;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // initiate environemnt
+    let worker = near_workspaces::sandbox().await?;
+
+    // deploy contracts
+    let ft_wasm = near_workspaces::compile_project(JOY_TOKEN_PROJECT_PATH).await?;
+    let ft_contract = worker.dev_deploy(&ft_wasm).await?;
+    let nft_pet_wasm = near_workspaces::compile_project(NFT_PET_PROJECT_PATH).await?;
+    let nft_pet_contract = worker.dev_deploy(&nft_pet_wasm).await?;
+    let marketplace_wasm = near_workspaces::compile_project(MARKETPLACE_PROJECT_PATH).await?;
+    let marketplace_contract = worker.dev_deploy(&marketplace_wasm).await?;
+
+    let owner = worker.root_account().unwrap();
+
+    let owner_ft = owner
+        .create_subaccount("ft")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let owner_nft_pet = owner
+        .create_subaccount("nft_pet")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    // the game contract mints pets and locks them while they're staked
+    let game = owner
+        .create_subaccount("game")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let treasury = owner
+        .create_subaccount("treasury")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Create Alice Account
+    let alice = owner
+        .create_subaccount("alice")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+    // Create Bob Account
+    let bob = owner
+        .create_subaccount("bob")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+    // Create Carol Account
+    let carol = owner
+        .create_subaccount("carol")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    ft_contract
+        .call("new_default_meta")
+        .args_json(json!({
+            "owner_id": owner_ft.id(),
+            "total_supply": U128::from(parse_near!("1,000,000,000 N")),
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    nft_pet_contract
+        .call("new_default_meta")
+        .args_json(json!({
+            "owner_id": owner_nft_pet.id()
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner_nft_pet
+        .call(nft_pet_contract.id(), "add_authorized_minter")
+        .args_json(json!({
+            "account_id": game.id()
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    marketplace_contract
+        .call("new")
+        .args_json(json!({
+            "owner_id": owner.id(),
+            "ft_contract_id": ft_contract.id()
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Register JOY accounts and give Bob and Carol some JOY to spend
+    for account in [&alice, &bob, &carol, &treasury, marketplace_contract.as_account()] {
+        storage_deposit(&owner_ft, &ft_contract, account).await?;
+    }
+    for account in [&bob, &carol] {
+        owner_ft
+            .call(ft_contract.id(), "ft_transfer")
+            .args_json(json!({
+                "receiver_id": account.id(),
+                "amount": U128(SALE_PRICE * 10)
+            }))
+            .deposit(DEFAULT_DEPOSIT)
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    // Mint 4 pets for Alice
+    for token_id in ["1", "2", "3", "4"] {
+        game.call(nft_pet_contract.id(), "nft_mint")
+            .args_json(json!({
+                "token_id": token_id,
+                "metadata": {"title": "Joychi"},
+                "receiver_id": alice.id()
+            }))
+            .deposit(NearToken::from_millinear(100))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    test_set_royalty(&owner, &alice, &treasury, &marketplace_contract, &nft_pet_contract).await?;
+    test_list_pet(&alice, &marketplace_contract, &nft_pet_contract).await?;
+    test_buy_pet(&alice, &bob, &treasury, &marketplace_contract, &nft_pet_contract, &ft_contract).await?;
+    test_buy_locked_pet(&alice, &bob, &game, &marketplace_contract, &nft_pet_contract, &ft_contract).await?;
+    test_english_auction(&worker, &alice, &bob, &carol, &marketplace_contract, &nft_pet_contract, &ft_contract).await?;
+    test_dutch_auction(&worker, &alice, &bob, &marketplace_contract, &nft_pet_contract, &ft_contract).await?;
+
+    Ok(())
+}
+
+async fn list_pet(
+    seller: &Account,
+    token_id: &str,
+    marketplace_contract: &Contract,
+    nft_pet_contract: &Contract,
+) -> anyhow::Result<()> {
+    seller
+        .call(nft_pet_contract.id(), "nft_approve")
+        .args_json(json!({
+            "token_id": token_id,
+            "account_id": marketplace_contract.id(),
+            "msg": json!({"sale_conditions": U128(SALE_PRICE)}).to_string()
+        }))
+        .deposit(NearToken::from_millinear(10))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+async fn bid(
+    bidder: &Account,
+    auction_id: u64,
+    amount: u128,
+    marketplace_contract: &Contract,
+    ft_contract: &Contract,
+) -> anyhow::Result<()> {
+    bidder
+        .call(ft_contract.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": marketplace_contract.id(),
+            "amount": U128(amount),
+            "msg": json!({"auction_id": auction_id.to_string()}).to_string()
+        }))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+async fn start_auction(
+    seller: &Account,
+    token_id: &str,
+    args: serde_json::Value,
+    marketplace_contract: &Contract,
+    nft_pet_contract: &Contract,
+) -> anyhow::Result<()> {
+    seller
+        .call(nft_pet_contract.id(), "nft_transfer_call")
+        .args_json(json!({
+            "receiver_id": marketplace_contract.id(),
+            "token_id": token_id,
+            "msg": args.to_string()
+        }))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+async fn nft_owner(user: &Account, token_id: &str, nft_pet_contract: &Contract) -> anyhow::Result<String> {
+    let token: JsonToken = user
+        .call(nft_pet_contract.id(), "nft_token")
+        .args_json(json!({"token_id": token_id}))
+        .view()
+        .await?
+        .json()?;
+    Ok(token.owner_id.to_string())
+}
+
+async fn buy_pet(
+    buyer: &Account,
+    token_id: &str,
+    marketplace_contract: &Contract,
+    nft_pet_contract: &Contract,
+    ft_contract: &Contract,
+) -> anyhow::Result<()> {
+    buyer
+        .call(ft_contract.id(), "ft_transfer_call")
+        .args_json(json!({
+            "receiver_id": marketplace_contract.id(),
+            "amount": U128(SALE_PRICE),
+            "msg": json!({"nft_contract_id": nft_pet_contract.id(), "token_id": token_id}).to_string()
+        }))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+pub async fn test_set_royalty(
+    owner: &Account,
+    user: &Account,
+    treasury: &Account,
+    marketplace_contract: &Contract,
+    nft_pet_contract: &Contract,
+) -> anyhow::Result<()> {
+    // Tokens of unsupported NFT contracts can't be listed
+    user.call(marketplace_contract.id(), "storage_deposit")
+        .args_json(json!({}))
+        .deposit(NearToken::from_millinear(100))
+        .transact()
+        .await?
+        .into_result()?;
+    let list_result = user
+        .call(nft_pet_contract.id(), "nft_approve")
+        .args_json(json!({
+            "token_id": "1",
+            "account_id": marketplace_contract.id(),
+            "msg": json!({"sale_conditions": U128(SALE_PRICE)}).to_string()
+        }))
+        .deposit(NearToken::from_millinear(10))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(!list_result.receipt_failures().is_empty());
+
+    // Only the marketplace owner can configure royalties
+    let royalty_result = user
+        .call(marketplace_contract.id(), "set_nft_contract_royalty")
+        .args_json(json!({
+            "nft_contract_id": nft_pet_contract.id(),
+            "receiver_id": treasury.id(),
+            "basis_points": ROYALTY_BASIS_POINTS
+        }))
+        .transact()
+        .await?;
+    assert!(royalty_result.is_failure());
+
+    owner
+        .call(marketplace_contract.id(), "set_nft_contract_royalty")
+        .args_json(json!({
+            "nft_contract_id": nft_pet_contract.id(),
+            "receiver_id": treasury.id(),
+            "basis_points": ROYALTY_BASIS_POINTS
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    println!("      Passed ✅ test_set_royalty");
+    Ok(())
+}
+
+pub async fn test_list_pet(
+    user: &Account,
+    marketplace_contract: &Contract,
+    nft_pet_contract: &Contract,
+) -> anyhow::Result<()> {
+    list_pet(user, "1", marketplace_contract, nft_pet_contract).await?;
+
+    let sale: serde_json::Value = user
+        .call(marketplace_contract.id(), "get_sale")
+        .args_json(json!({"nft_contract_token": format!("{}.1", nft_pet_contract.id())}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(sale["owner_id"], user.id().as_str());
+    assert_eq!(sale["sale_conditions"], SALE_PRICE.to_string());
+
+    println!("      Passed ✅ test_list_pet");
+    Ok(())
+}
+
+pub async fn test_buy_pet(
+    seller: &Account,
+    buyer: &Account,
+    treasury: &Account,
+    marketplace_contract: &Contract,
+    nft_pet_contract: &Contract,
+    ft_contract: &Contract,
+) -> anyhow::Result<()> {
+    let buyer_balance = ft_balance_of(ft_contract, buyer.id()).await?;
+
+    buy_pet(buyer, "1", marketplace_contract, nft_pet_contract, ft_contract).await?;
+
+    // The pet is moved to the buyer and the JOY is split between the seller and the royalty receiver
+    let token: JsonToken = buyer
+        .call(nft_pet_contract.id(), "nft_token")
+        .args_json(json!({"token_id": "1"}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(token.owner_id.as_str(), buyer.id().as_str());
+
+    let royalty = SALE_PRICE * ROYALTY_BASIS_POINTS as u128 / 10_000;
+    assert_eq!(ft_balance_of(ft_contract, buyer.id()).await?, buyer_balance - SALE_PRICE);
+    assert_eq!(ft_balance_of(ft_contract, seller.id()).await?, SALE_PRICE - royalty);
+    assert_eq!(ft_balance_of(ft_contract, treasury.id()).await?, royalty);
+
+    println!("      Passed ✅ test_buy_pet");
+    Ok(())
+}
+
+pub async fn test_buy_locked_pet(
+    seller: &Account,
+    buyer: &Account,
+    game: &Account,
+    marketplace_contract: &Contract,
+    nft_pet_contract: &Contract,
+    ft_contract: &Contract,
+) -> anyhow::Result<()> {
+    list_pet(seller, "2", marketplace_contract, nft_pet_contract).await?;
+
+    // The pet gets staked in the game after it was listed
+    game.call(nft_pet_contract.id(), "nft_lock")
+        .args_json(json!({"token_id": "2", "owner_id": seller.id(), "reason": "staking"}))
+        .deposit(DEFAULT_DEPOSIT)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let buyer_balance = ft_balance_of(ft_contract, buyer.id()).await?;
+    buy_pet(buyer, "2", marketplace_contract, nft_pet_contract, ft_contract).await?;
+
+    // The buyer gets the JOY back and the stale sale is removed
+    let token: JsonToken = buyer
+        .call(nft_pet_contract.id(), "nft_token")
+        .args_json(json!({"token_id": "2"}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(token.owner_id.as_str(), seller.id().as_str());
+    assert_eq!(ft_balance_of(ft_contract, buyer.id()).await?, buyer_balance);
+
+    let sale: Option<serde_json::Value> = buyer
+        .call(marketplace_contract.id(), "get_sale")
+        .args_json(json!({"nft_contract_token": format!("{}.2", nft_pet_contract.id())}))
+        .view()
+        .await?
+        .json()?;
+    assert!(sale.is_none());
+
+    println!("      Passed ✅ test_buy_locked_pet");
+    Ok(())
+}
+
+pub async fn test_english_auction(
+    worker: &Worker<Sandbox>,
+    seller: &Account,
+    bidder: &Account,
+    winner: &Account,
+    marketplace_contract: &Contract,
+    nft_pet_contract: &Contract,
+    ft_contract: &Contract,
+) -> anyhow::Result<()> {
+    let now = worker.view_block().await?.timestamp();
+    let seller_balance = ft_balance_of(ft_contract, seller.id()).await?;
+    let bidder_balance = ft_balance_of(ft_contract, bidder.id()).await?;
+
+    // The pet is escrowed by the marketplace for 10 minutes
+    start_auction(
+        seller,
+        "3",
+        json!({
+            "kind": "English",
+            "start_price": U128(100),
+            "reserve_price": U128(150),
+            "min_bid_increment": U128(10),
+            "end_at": (now + 600_000_000_000).to_string()
+        }),
+        marketplace_contract,
+        nft_pet_contract,
+    )
+    .await?;
+    assert_eq!(nft_owner(seller, "3", nft_pet_contract).await?, marketplace_contract.id().as_str());
+
+    // An outbid bidder gets the JOY back
+    bid(bidder, 1, 100, marketplace_contract, ft_contract).await?;
+    bid(winner, 1, 150, marketplace_contract, ft_contract).await?;
+    assert_eq!(ft_balance_of(ft_contract, bidder.id()).await?, bidder_balance);
+
+    // Bids must beat the highest bid by the minimum increment, rejected bids are refunded by the JOY contract
+    bid(bidder, 1, 155, marketplace_contract, ft_contract).await?;
+    assert_eq!(ft_balance_of(ft_contract, bidder.id()).await?, bidder_balance);
+    let auction: serde_json::Value = seller
+        .call(marketplace_contract.id(), "get_auction")
+        .args_json(json!({"auction_id": "1"}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(auction["highest_bidder"], winner.id().as_str());
+
+    // The auction can't be settled before it ends
+    let early_settle = seller
+        .call(marketplace_contract.id(), "settle_auction")
+        .args_json(json!({"auction_id": "1"}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(early_settle.is_failure());
+
+    worker.fast_forward(1_000).await?;
+
+    seller
+        .call(marketplace_contract.id(), "settle_auction")
+        .args_json(json!({"auction_id": "1"}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let royalty = 150 * ROYALTY_BASIS_POINTS as u128 / 10_000;
+    assert_eq!(nft_owner(seller, "3", nft_pet_contract).await?, winner.id().as_str());
+    assert_eq!(ft_balance_of(ft_contract, seller.id()).await?, seller_balance + 150 - royalty);
+
+    println!("      Passed ✅ test_english_auction");
+    Ok(())
+}
+
+pub async fn test_dutch_auction(
+    worker: &Worker<Sandbox>,
+    seller: &Account,
+    buyer: &Account,
+    marketplace_contract: &Contract,
+    nft_pet_contract: &Contract,
+    ft_contract: &Contract,
+) -> anyhow::Result<()> {
+    let now = worker.view_block().await?.timestamp();
+    let buyer_balance = ft_balance_of(ft_contract, buyer.id()).await?;
+
+    // The price goes down from 1000 to 100 JOY over an hour
+    start_auction(
+        seller,
+        "4",
+        json!({
+            "kind": "Dutch",
+            "start_price": U128(SALE_PRICE),
+            "reserve_price": U128(100),
+            "end_at": (now + 3_600_000_000_000).to_string()
+        }),
+        marketplace_contract,
+        nft_pet_contract,
+    )
+    .await?;
+
+    // The first bid at the current price wins and the difference is refunded
+    bid(buyer, 2, SALE_PRICE, marketplace_contract, ft_contract).await?;
+
+    let spent = buyer_balance - ft_balance_of(ft_contract, buyer.id()).await?;
+    assert!(spent > 100 && spent <= SALE_PRICE);
+    assert_eq!(nft_owner(seller, "4", nft_pet_contract).await?, buyer.id().as_str());
+
+    let auction: Option<serde_json::Value> = buyer
+        .call(marketplace_contract.id(), "get_auction")
+        .args_json(json!({"auction_id": "2"}))
+        .view()
+        .await?
+        .json()?;
+    assert!(auction.is_none());
+
+    println!("      Passed ✅ test_dutch_auction");
+    Ok(())
+}
@@ -0,0 +1,337 @@
+use near_gas::NearGas;
+use near_token::NearToken;
+use near_units::parse_near;
+use serde_json::json;
+mod helpers;
+use near_sdk::json_types::U128;
+use near_workspaces::{Account, Contract};
+
+use helpers::{ft_balance_of, storage_deposit};
+
+const JOY_TOKEN_PROJECT_PATH: &str = "../ft_token";
+const NFT_PET_PROJECT_PATH: &str = "../nft_pet";
+const NFT_ITEM_PROJECT_PATH: &str = "../nft_item";
+const JOYCHI_PROJECT_PATH: &str = "../joy_v1";
+const REF_EXCHANGE_WASM_FILEPATH: &str = "../res/ref_exchange_153.wasm";
+// wNEAR is imported from mainnet
+const WRAP_NEAR_ACCOUNT_ID: &str = "wrap.near";
+
+const INITIAL_NEAR: NearToken = NearToken::from_near(30);
+
+const DEFAULT_DEPOSIT: NearToken = NearToken::from_yoctonear(1);
+const DEFAULT_GAS: NearGas = NearGas::from_tgas(300);
+
+// The pool holds 1 wNEAR for 10 JOY
+const POOL_WNEAR: u128 = parse_near!("100 N");
+const POOL_JOY: u128 = parse_near!("1000 N");
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // initiate environemnt
+    let worker = near_workspaces::sandbox().await?;
+    let mainnet = near_workspaces::mainnet().await?;
+
+    // deploy contracts
+    let ft_wasm = near_workspaces::compile_project(JOY_TOKEN_PROJECT_PATH).await?;
+    let ft_contract = worker.dev_deploy(&ft_wasm).await?;
+    let nft_pet_wasm = near_workspaces::compile_project(NFT_PET_PROJECT_PATH).await?;
+    let nft_pet_contract = worker.dev_deploy(&nft_pet_wasm).await?;
+    let nft_item_wasm = near_workspaces::compile_project(NFT_ITEM_PROJECT_PATH).await?;
+    let nft_item_contract = worker.dev_deploy(&nft_item_wasm).await?;
+    let joychi_wasm = near_workspaces::compile_project(JOYCHI_PROJECT_PATH).await?;
+    let joychi_contract = worker.dev_deploy(&joychi_wasm).await?;
+    let ref_wasm = std::fs::read(REF_EXCHANGE_WASM_FILEPATH)?;
+    let ref_contract = worker.dev_deploy(&ref_wasm).await?;
+    let wnear_contract = worker
+        .import_contract(&WRAP_NEAR_ACCOUNT_ID.parse()?, &mainnet)
+        .initial_balance(NearToken::from_near(100))
+        .transact()
+        .await?;
+
+    let owner = worker.root_account().unwrap();
+
+    // Create Alice Account, pays for her items with NEAR
+    let alice = owner
+        .create_subaccount("alice")
+        .initial_balance(INITIAL_NEAR)
+        .transact()
+        .await?
+        .into_result()?;
+
+    ft_contract
+        .call("new_default_meta")
+        .args_json(json!({
+            "owner_id": owner.id(),
+            "total_supply": U128::from(parse_near!("1,000,000,000 N")),
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    wnear_contract.call("new").transact().await?.into_result()?;
+
+    for nft_contract in [&nft_pet_contract, &nft_item_contract] {
+        nft_contract
+            .call("new_default_meta")
+            .args_json(json!({
+                "owner_id": owner.id()
+            }))
+            .transact()
+            .await?
+            .into_result()?;
+
+        owner
+            .call(nft_contract.id(), "add_authorized_minter")
+            .args_json(json!({
+                "account_id": joychi_contract.id()
+            }))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    owner
+        .call(joychi_contract.id(), "init")
+        .args_json(json!({
+            "nft_addr": nft_pet_contract.id(),
+            "nft_item_addr": nft_item_contract.id(),
+            "ft_addr": ft_contract.id()
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    ref_contract
+        .call("new")
+        .args_json(json!({
+            "owner_id": owner.id(),
+            "exchange_fee": 4,
+            "referral_fee": 1,
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // Everyone holding JOY or wNEAR is registered on the tokens
+    for token in [&ft_contract, &wnear_contract] {
+        for account in [&owner, &alice, ref_contract.as_account(), joychi_contract.as_account()] {
+            storage_deposit(&owner, token, account).await?;
+        }
+    }
+
+    let pool_id = test_create_pool(&owner, &ref_contract, &ft_contract, &wnear_contract).await?;
+    test_buy_item_with_near(
+        &owner,
+        &alice,
+        &joychi_contract,
+        &ref_contract,
+        &ft_contract,
+        &wnear_contract,
+        pool_id,
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn ref_deposit(owner: &Account, token: &Contract, ref_contract: &Contract, amount: u128) -> anyhow::Result<()> {
+    owner
+        .call(token.id(), "ft_transfer_call")
+        .args_json(json!({"receiver_id": ref_contract.id(), "amount": U128(amount), "msg": ""}))
+        .deposit(DEFAULT_DEPOSIT)
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+    Ok(())
+}
+
+async fn test_create_pool(
+    owner: &Account,
+    ref_contract: &Contract,
+    ft_contract: &Contract,
+    wnear_contract: &Contract,
+) -> anyhow::Result<u64> {
+    owner
+        .call(ref_contract.id(), "storage_deposit")
+        .args_json(json!({"account_id": owner.id(), "registration_only": false}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner
+        .call(ref_contract.id(), "extend_whitelisted_tokens")
+        .args_json(json!({"tokens": [wnear_contract.id(), ft_contract.id()]}))
+        .deposit(DEFAULT_DEPOSIT)
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool_id: u64 = owner
+        .call(ref_contract.id(), "add_simple_pool")
+        .args_json(json!({"tokens": [wnear_contract.id(), ft_contract.id()], "fee": 25}))
+        .deposit(NearToken::from_millinear(100))
+        .transact()
+        .await?
+        .json()?;
+
+    // Wrap the NEAR, deposit both tokens and add them to the pool
+    owner
+        .call(wnear_contract.id(), "near_deposit")
+        .deposit(NearToken::from_yoctonear(POOL_WNEAR))
+        .transact()
+        .await?
+        .into_result()?;
+
+    ref_deposit(owner, wnear_contract, ref_contract, POOL_WNEAR).await?;
+    ref_deposit(owner, ft_contract, ref_contract, POOL_JOY).await?;
+
+    owner
+        .call(ref_contract.id(), "add_liquidity")
+        .args_json(json!({"pool_id": pool_id, "amounts": [U128(POOL_WNEAR), U128(POOL_JOY)]}))
+        .deposit(NearToken::from_millinear(10))
+        .transact()
+        .await?
+        .into_result()?;
+
+    let pool: serde_json::Value = owner
+        .call(ref_contract.id(), "get_pool")
+        .args_json(json!({"pool_id": pool_id}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(pool["amounts"][0], POOL_WNEAR.to_string());
+    assert_eq!(pool["amounts"][1], POOL_JOY.to_string());
+
+    println!("      Passed ✅ test_create_pool");
+    Ok(pool_id)
+}
+
+async fn test_buy_item_with_near(
+    owner: &Account,
+    alice: &Account,
+    joychi_contract: &Contract,
+    ref_contract: &Contract,
+    ft_contract: &Contract,
+    wnear_contract: &Contract,
+    pool_id: u64,
+) -> anyhow::Result<()> {
+    // The game keeps the wNEAR and the JOY of the swaps on Ref
+    owner
+        .call(ref_contract.id(), "storage_deposit")
+        .args_json(json!({"account_id": joychi_contract.id(), "registration_only": false}))
+        .deposit(NearToken::from_near(1))
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner
+        .call(joychi_contract.id(), "set_ref_exchange")
+        .args_json(json!({"ref_exchange": {
+            "ref_exchange_id": ref_contract.id(),
+            "wnear_id": wnear_contract.id(),
+            "pool_id": pool_id,
+        }}))
+        .transact()
+        .await?
+        .into_result()?;
+
+    // A pet for alice, creating it burns a bit of JOY
+    owner
+        .call(ft_contract.id(), "ft_transfer")
+        .args_json(json!({"receiver_id": alice.id(), "amount": U128(parse_near!("1 N"))}))
+        .deposit(DEFAULT_DEPOSIT)
+        .transact()
+        .await?
+        .into_result()?;
+
+    owner
+        .call(joychi_contract.id(), "create_species")
+        .args_json(json!({
+            "need_evol_item": false,
+            "evol_item_id": 0,
+            "name_spec": "JOY1",
+            "pet_evolution": [{"image": "evolution_1_image.com", "name": "Gold", "attack_win_rate": 5, "next_evolution_level": 2}],
+        }))
+        .transact()
+        .await?
+        .into_result()?;
+
+    alice
+        .call(joychi_contract.id(), "create_pet")
+        .args_json(json!({"name": "Pet1"}))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?
+        .into_result()?;
+
+    // A 5 JOY item, and one the pool can't pay for
+    for price in [parse_near!("5 N"), parse_near!("100,000 N")] {
+        owner
+            .call(joychi_contract.id(), "create_item_immidiate")
+            .args_json(json!({
+                "name": "hat",
+                "price": price,
+                "points": 100,
+                "time_extension": 100021310000u128,
+                "price_delta": 0,
+                "stock": 5,
+                "shield": 10,
+                "is_revival": false,
+            }))
+            .transact()
+            .await?
+            .into_result()?;
+    }
+
+    let joy_before = ft_balance_of(ft_contract, alice.id()).await?;
+
+    // 1 NEAR gets a bit less than 10 JOY, 5 are burned for the item and the rest goes to alice
+    let result = alice
+        .call(joychi_contract.id(), "buy_item_immidiate_with_near")
+        .args_json(json!({"pet_id": 1, "item_id": 1}))
+        .deposit(NearToken::from_near(1))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(result.json::<bool>()?);
+
+    let pet: serde_json::Value = alice
+        .call(joychi_contract.id(), "get_pet_by_pet_id")
+        .args_json(json!({"pet_id": 1}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(pet["items"].as_array().unwrap().len(), 1);
+
+    let joy_after = ft_balance_of(ft_contract, alice.id()).await?;
+    assert!(joy_after > joy_before && joy_after - joy_before < parse_near!("5 N"));
+    assert_eq!(ft_balance_of(ft_contract, joychi_contract.id()).await?, 0);
+
+    // The swap can't give enough JOY, the NEAR is refunded
+    let near_before = alice.view_account().await?.balance;
+
+    let result = alice
+        .call(joychi_contract.id(), "buy_item_immidiate_with_near")
+        .args_json(json!({"pet_id": 1, "item_id": 2}))
+        .deposit(NearToken::from_near(1))
+        .gas(DEFAULT_GAS)
+        .transact()
+        .await?;
+    assert!(!result.json::<bool>()?);
+
+    let near_after = alice.view_account().await?.balance;
+    assert!(near_before.as_yoctonear() - near_after.as_yoctonear() < parse_near!("0.1 N"));
+
+    let pet: serde_json::Value = alice
+        .call(joychi_contract.id(), "get_pet_by_pet_id")
+        .args_json(json!({"pet_id": 1}))
+        .view()
+        .await?
+        .json()?;
+    assert_eq!(pet["items"].as_array().unwrap().len(), 1);
+
+    println!("      Passed ✅ test_buy_item_with_near");
+    Ok(())
+}
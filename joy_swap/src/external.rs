@@ -0,0 +1,11 @@
+use crate::*;
+use near_sdk::ext_contract;
+
+//external contract calls
+
+//initiate a cross contract call to a token of the pool to send it to an account
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+#[ext_contract(ext_ft)]
+trait ExtFungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
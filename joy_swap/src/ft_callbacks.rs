@@ -0,0 +1,52 @@
+use crate::*;
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+
+/// transfer callbacks from the JOY and wNEAR contracts
+
+//the msg passed to ft_transfer_call to swap, an empty msg deposits the tokens
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SwapArgs {
+    //the swap fails if it returns less than this
+    pub min_amount_out: U128,
+    //the account getting the other token, the sender by default
+    pub receiver_id: Option<AccountId>,
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for Contract {
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        let token_id = env::predecessor_account_id();
+        assert!(
+            token_id == self.joy_token_id || token_id == self.wnear_token_id,
+            "Only JOY and wNEAR are accepted"
+        );
+
+        if msg.is_empty() {
+            //only registered accounts paid for their deposits, the tokens of the others are refunded
+            if !self.deposits.contains_key(&sender_id) {
+                env::log_str(&format!("{} isn't registered, the deposit is refunded", sender_id));
+                return PromiseOrValue::Value(amount);
+            }
+            self.internal_deposit(&sender_id, &token_id, amount.0);
+            return PromiseOrValue::Value(U128(0));
+        }
+
+        let args: SwapArgs = near_sdk::serde_json::from_str(&msg).expect("Not valid SwapArgs");
+        let receiver_id = args.receiver_id.unwrap_or(sender_id);
+        //the output is credited back to the receiver if it can't be sent, so the receiver must be registered
+        if !self.deposits.contains_key(&receiver_id) {
+            env::log_str(&format!("{} isn't registered, the swap is refunded", receiver_id));
+            return PromiseOrValue::Value(amount);
+        }
+        let (token_out, amount_out) = self.internal_swap(&token_id, amount.0, args.min_amount_out.0);
+        self.internal_send_tokens(&receiver_id, &token_out, amount_out);
+
+        PromiseOrValue::Value(U128(0))
+    }
+}
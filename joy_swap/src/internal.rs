@@ -0,0 +1,135 @@
+use crate::*;
+
+//used to multiply the reserves without overflowing
+#[allow(clippy::assign_op_pattern, clippy::manual_div_ceil)]
+mod u256 {
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+use u256::U256;
+
+//a * b / c rounded down
+pub(crate) fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    (U256::from(a) * U256::from(b) / U256::from(c)).as_u128()
+}
+
+//a * b / c rounded up, so the pool never gives out more than it takes
+pub(crate) fn mul_div_ceil(a: u128, b: u128, c: u128) -> u128 {
+    let c = U256::from(c);
+    ((U256::from(a) * U256::from(b) + c - 1) / c).as_u128()
+}
+
+//check that the last promise the callback waited on succeeded
+pub(crate) fn is_promise_success() -> bool {
+    assert_eq!(
+        env::promise_results_count(),
+        1,
+        "Contract expected a result on the callback"
+    );
+    matches!(env::promise_result(0), near_sdk::PromiseResult::Successful(_))
+}
+
+impl Contract {
+    //make sure that the caller is the owner of the contract
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only the contract owner can call this method"
+        );
+    }
+
+    pub(crate) fn internal_get_deposit(&self, account_id: &AccountId) -> AccountDeposit {
+        self.deposits.get(account_id).expect("Account isn't registered")
+    }
+
+    //the reserves of the pool ordered as (token in, token out)
+    pub(crate) fn internal_reserves(&self, token_in: &AccountId) -> (Balance, Balance) {
+        if *token_in == self.joy_token_id {
+            (self.reserve_joy, self.reserve_wnear)
+        } else if *token_in == self.wnear_token_id {
+            (self.reserve_wnear, self.reserve_joy)
+        } else {
+            env::panic_str("Token isn't in the pool")
+        }
+    }
+
+    //the constant product amount out after the fee is taken from the amount in
+    pub(crate) fn internal_amount_out(&self, token_in: &AccountId, amount_in: Balance) -> Balance {
+        let (reserve_in, reserve_out) = self.internal_reserves(token_in);
+        if reserve_in == 0 || reserve_out == 0 {
+            return 0;
+        }
+
+        let amount_in_with_fee = U256::from(amount_in) * U256::from(FEE_DIVISOR - self.fee_bps);
+        let numerator = amount_in_with_fee * U256::from(reserve_out);
+        let denominator = U256::from(reserve_in) * U256::from(FEE_DIVISOR) + amount_in_with_fee;
+
+        (numerator / denominator).as_u128()
+    }
+
+    //swap amount_in of token_in for the other token, returns it and the amount out
+    pub(crate) fn internal_swap(
+        &mut self,
+        token_in: &AccountId,
+        amount_in: Balance,
+        min_amount_out: Balance,
+    ) -> (AccountId, Balance) {
+        let amount_out = self.internal_amount_out(token_in, amount_in);
+        assert!(amount_out > 0, "Not enough liquidity for the swap");
+        assert!(
+            amount_out >= min_amount_out,
+            "Slippage error: the swap would return {}",
+            amount_out
+        );
+
+        if *token_in == self.joy_token_id {
+            self.reserve_joy += amount_in;
+            self.reserve_wnear -= amount_out;
+            (self.wnear_token_id.clone(), amount_out)
+        } else {
+            self.reserve_wnear += amount_in;
+            self.reserve_joy -= amount_out;
+            (self.joy_token_id.clone(), amount_out)
+        }
+    }
+
+    //credit tokens to the deposits of a registered account
+    pub(crate) fn internal_deposit(&mut self, account_id: &AccountId, token_id: &AccountId, amount: Balance) {
+        let mut deposit = self.internal_get_deposit(account_id);
+        if *token_id == self.joy_token_id {
+            deposit.joy = U128(deposit.joy.0 + amount);
+        } else {
+            deposit.wnear = U128(deposit.wnear.0 + amount);
+        }
+        self.deposits.insert(account_id, &deposit);
+    }
+
+    pub(crate) fn internal_withdraw_deposit(&mut self, account_id: &AccountId, token_id: &AccountId, amount: Balance) {
+        let mut deposit = self.internal_get_deposit(account_id);
+        let balance = if *token_id == self.joy_token_id {
+            &mut deposit.joy
+        } else if *token_id == self.wnear_token_id {
+            &mut deposit.wnear
+        } else {
+            env::panic_str("Token isn't in the pool")
+        };
+        assert!(amount > 0 && amount <= balance.0, "Not enough tokens deposited");
+        *balance = U128(balance.0 - amount);
+        self.deposits.insert(account_id, &deposit);
+    }
+
+    //send tokens to an account, they are credited back to its deposits if the transfer fails
+    pub(crate) fn internal_send_tokens(&self, receiver_id: &AccountId, token_id: &AccountId, amount: Balance) -> Promise {
+        ext_ft::ext(token_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_FT_TRANSFER)
+            .ft_transfer(receiver_id.clone(), U128(amount), Some("joy_swap".to_string()))
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_TRANSFER)
+                    .resolve_transfer(receiver_id.clone(), token_id.clone(), U128(amount)),
+            )
+    }
+}
@@ -0,0 +1,231 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::LookupMap;
+use near_sdk::json_types::U128;
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{
+    assert_one_yocto, env, near_bindgen, AccountId, Balance, BorshStorageKey, Gas,
+    PanicOnDefault, Promise, PromiseOrValue,
+};
+
+use crate::external::*;
+use crate::internal::*;
+use near_sdk::env::STORAGE_PRICE_PER_BYTE;
+
+mod external;
+mod ft_callbacks;
+mod internal;
+mod views;
+
+//GAS constants to attach to calls
+const GAS_FOR_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(10_000_000_000_000);
+
+//1 yoctoNEAR is attached to ft_transfer
+const ONE_YOCTO: Balance = 1;
+
+//the storage of the deposits and the shares of an account
+const STORAGE_PER_ACCOUNT: u128 = 250 * STORAGE_PRICE_PER_BYTE;
+
+//the fee is in basis points of the amount swapped and is capped to 10%
+const FEE_DIVISOR: u32 = 10_000;
+const MAX_FEE_BPS: u32 = 1_000;
+
+//the shares minted to the first liquidity provider
+const INIT_SHARES_SUPPLY: u128 = 1_000_000_000_000_000_000_000_000;
+
+//the tokens an account deposited and can add as liquidity or withdraw
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AccountDeposit {
+    pub joy: U128,
+    pub wnear: U128,
+}
+
+impl Default for AccountDeposit {
+    fn default() -> Self {
+        Self {
+            joy: U128(0),
+            wnear: U128(0),
+        }
+    }
+}
+
+//main contract struct to store all the information
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+pub struct Contract {
+    //keep track of the owner of the contract
+    pub owner_id: AccountId,
+
+    //the two tokens of the pool
+    pub joy_token_id: AccountId,
+    pub wnear_token_id: AccountId,
+
+    //the fee taken on swaps in basis points, it stays in the pool for the liquidity providers
+    pub fee_bps: u32,
+
+    //the tokens held by the pool
+    pub reserve_joy: Balance,
+    pub reserve_wnear: Balance,
+
+    //the LP shares of every liquidity provider and their total
+    pub shares: LookupMap<AccountId, Balance>,
+    pub total_shares: Balance,
+
+    //the tokens deposited by registered accounts, not yet in the pool
+    pub deposits: LookupMap<AccountId, AccountDeposit>,
+}
+
+/// Helper structure for keys of the persistent collections.
+#[derive(BorshStorageKey, BorshSerialize)]
+pub enum StorageKey {
+    Shares,
+    Deposits,
+}
+
+#[near_bindgen]
+impl Contract {
+    /*
+        initialization function (can only be called once).
+        this initializes the contract with an owner, the two tokens of the pool and the swap fee.
+    */
+    #[init]
+    pub fn new(owner_id: AccountId, joy_token_id: AccountId, wnear_token_id: AccountId, fee_bps: u32) -> Self {
+        assert!(fee_bps <= MAX_FEE_BPS, "Fee can't be above {} basis points", MAX_FEE_BPS);
+
+        Self {
+            owner_id,
+            joy_token_id,
+            wnear_token_id,
+            fee_bps,
+            reserve_joy: 0,
+            reserve_wnear: 0,
+            shares: LookupMap::new(StorageKey::Shares),
+            total_shares: 0,
+            deposits: LookupMap::new(StorageKey::Deposits),
+        }
+    }
+
+    //register an account so it can deposit tokens and provide liquidity, the excess deposit is refunded
+    #[payable]
+    pub fn storage_deposit(&mut self, account_id: Option<AccountId>) {
+        let storage_account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let deposit = env::attached_deposit();
+
+        if self.deposits.contains_key(&storage_account_id) {
+            Promise::new(env::predecessor_account_id()).transfer(deposit);
+            return;
+        }
+
+        assert!(
+            deposit >= STORAGE_PER_ACCOUNT,
+            "Requires minimum deposit of {}",
+            STORAGE_PER_ACCOUNT
+        );
+        self.deposits.insert(&storage_account_id, &AccountDeposit::default());
+
+        let refund = deposit - STORAGE_PER_ACCOUNT;
+        if refund > 0 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+    }
+
+    pub fn set_fee(&mut self, fee_bps: u32) {
+        self.assert_owner();
+        assert!(fee_bps <= MAX_FEE_BPS, "Fee can't be above {} basis points", MAX_FEE_BPS);
+        self.fee_bps = fee_bps;
+    }
+
+    /*
+        add deposited tokens to the pool for LP shares. After the first provider, only the amounts
+        keeping the pool ratio are taken, the rest stays deposited.
+    */
+    pub fn add_liquidity(&mut self, joy_amount: U128, wnear_amount: U128, min_shares: U128) -> U128 {
+        let account_id = env::predecessor_account_id();
+        let mut deposit = self.internal_get_deposit(&account_id);
+        assert!(
+            joy_amount.0 <= deposit.joy.0 && wnear_amount.0 <= deposit.wnear.0,
+            "Not enough tokens deposited"
+        );
+
+        let (joy_added, wnear_added, new_shares) = if self.total_shares == 0 {
+            assert!(joy_amount.0 > 0 && wnear_amount.0 > 0, "Both tokens must be added");
+            (joy_amount.0, wnear_amount.0, INIT_SHARES_SUPPLY)
+        } else {
+            let shares_by_joy = mul_div(joy_amount.0, self.total_shares, self.reserve_joy);
+            let shares_by_wnear = mul_div(wnear_amount.0, self.total_shares, self.reserve_wnear);
+            let new_shares = shares_by_joy.min(shares_by_wnear);
+            (
+                mul_div_ceil(new_shares, self.reserve_joy, self.total_shares),
+                mul_div_ceil(new_shares, self.reserve_wnear, self.total_shares),
+                new_shares,
+            )
+        };
+        assert!(new_shares > 0, "Not enough liquidity added");
+        assert!(new_shares >= min_shares.0, "Slippage error: {} shares below the minimum", new_shares);
+
+        deposit.joy = U128(deposit.joy.0 - joy_added);
+        deposit.wnear = U128(deposit.wnear.0 - wnear_added);
+        self.deposits.insert(&account_id, &deposit);
+
+        self.reserve_joy += joy_added;
+        self.reserve_wnear += wnear_added;
+        self.total_shares += new_shares;
+        let shares = self.shares.get(&account_id).unwrap_or(0);
+        self.shares.insert(&account_id, &(shares + new_shares));
+
+        U128(new_shares)
+    }
+
+    //burn LP shares for their part of the pool, the tokens go back to the deposits of the account
+    pub fn remove_liquidity(&mut self, shares: U128, min_joy_amount: U128, min_wnear_amount: U128) -> AccountDeposit {
+        let account_id = env::predecessor_account_id();
+        let account_shares = self.shares.get(&account_id).unwrap_or(0);
+        assert!(shares.0 > 0 && shares.0 <= account_shares, "Not enough shares");
+
+        let joy_amount = mul_div(shares.0, self.reserve_joy, self.total_shares);
+        let wnear_amount = mul_div(shares.0, self.reserve_wnear, self.total_shares);
+        assert!(
+            joy_amount >= min_joy_amount.0 && wnear_amount >= min_wnear_amount.0,
+            "Slippage error: the pool would return {} JOY and {} wNEAR",
+            joy_amount,
+            wnear_amount
+        );
+
+        self.reserve_joy -= joy_amount;
+        self.reserve_wnear -= wnear_amount;
+        self.total_shares -= shares.0;
+        self.shares.insert(&account_id, &(account_shares - shares.0));
+
+        let mut deposit = self.internal_get_deposit(&account_id);
+        deposit.joy = U128(deposit.joy.0 + joy_amount);
+        deposit.wnear = U128(deposit.wnear.0 + wnear_amount);
+        self.deposits.insert(&account_id, &deposit);
+
+        AccountDeposit {
+            joy: U128(joy_amount),
+            wnear: U128(wnear_amount),
+        }
+    }
+
+    //send deposited tokens back to the account
+    #[payable]
+    pub fn withdraw(&mut self, token_id: AccountId, amount: U128) -> Promise {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        self.internal_withdraw_deposit(&account_id, &token_id, amount.0);
+
+        self.internal_send_tokens(&account_id, &token_id, amount.0)
+    }
+
+    //a swap or a withdrawal that couldn't be sent is credited to the deposits of the receiver, who is registered
+    #[private]
+    pub fn resolve_transfer(&mut self, receiver_id: AccountId, token_id: AccountId, amount: U128) -> bool {
+        if is_promise_success() {
+            return true;
+        }
+
+        self.internal_deposit(&receiver_id, &token_id, amount.0);
+        false
+    }
+}
@@ -0,0 +1,45 @@
+use crate::*;
+
+//the state of the pool
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolInfo {
+    pub joy_token_id: AccountId,
+    pub wnear_token_id: AccountId,
+    pub reserve_joy: U128,
+    pub reserve_wnear: U128,
+    pub total_shares: U128,
+    pub fee_bps: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn get_pool(&self) -> PoolInfo {
+        PoolInfo {
+            joy_token_id: self.joy_token_id.clone(),
+            wnear_token_id: self.wnear_token_id.clone(),
+            reserve_joy: U128(self.reserve_joy),
+            reserve_wnear: U128(self.reserve_wnear),
+            total_shares: U128(self.total_shares),
+            fee_bps: self.fee_bps,
+        }
+    }
+
+    //the amount of the other token a swap would return now, to set min_amount_out
+    pub fn get_return(&self, token_in: AccountId, amount_in: U128) -> U128 {
+        U128(self.internal_amount_out(&token_in, amount_in.0))
+    }
+
+    pub fn get_shares(&self, account_id: AccountId) -> U128 {
+        U128(self.shares.get(&account_id).unwrap_or(0))
+    }
+
+    pub fn get_deposit(&self, account_id: AccountId) -> AccountDeposit {
+        self.deposits.get(&account_id).unwrap_or_default()
+    }
+
+    //the minimum deposit to register an account
+    pub fn storage_minimum_balance(&self) -> U128 {
+        U128(STORAGE_PER_ACCOUNT)
+    }
+}
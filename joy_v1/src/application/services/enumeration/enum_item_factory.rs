@@ -1,8 +1,9 @@
+use near_contract_standards::non_fungible_token::TokenId;
 use near_sdk::near_bindgen;
 
 use crate::models::{
     contract::{JoychiV1, JoychiV1Ext},
-    item_factory::{ItemEnum, ItemMetadata},
+    item_factory::{ItemEnum, ItemInstance, ItemMetadata},
     ItemId,
 };
 
@@ -22,4 +23,10 @@ impl ItemEnum for JoychiV1 {
 
         item
     }
+
+    fn get_item_instance_by_token_id(&self, token_id: TokenId) -> ItemInstance {
+        let item_instance = self.item_instance_by_token_id.get(&token_id).unwrap();
+
+        item_instance
+    }
 }
@@ -1,15 +1,107 @@
-use near_sdk::{near_bindgen, AccountId};
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId};
 
+use crate::application::services::infrastructure::impl_staking_and_mining::BASIS_POINTS;
 use crate::models::{
     contract::{JoychiV1, JoychiV1Ext},
-    staking_and_mining::{MiningData, StakingAndMiningEnum},
+    staking_and_mining::{MiningData, MiningDifficulty, MiningPreview, MiningShare, PoolBalance, PoolEligibilityCheck, PoolMetadata, StakingAndMiningEnum},
+    ItemId, PetId, PoolId,
 };
 
 #[near_bindgen]
 impl StakingAndMiningEnum for JoychiV1 {
+    fn get_pool_metadata_by_id(&self, pool_id: PoolId) -> Option<PoolMetadata> {
+        self.pool_metadata_by_id.get(&pool_id)
+    }
+
+    fn get_pending_staking_reward(&self, pet_id: PetId, pool_id: PoolId) -> U128 {
+        let pool = self.pool_metadata_by_id.get(&pool_id).expect("Staking pool doesn't exist");
+
+        pool.staked_pets
+            .iter()
+            .find(|staked| staked.nft_id == pet_id as u128)
+            .map(|staked| U128(self.internal_staking_reward(&pool, staked) - staked.reward_claimed))
+            .unwrap_or(U128(0))
+    }
+
+    fn get_pool_balance(&self, pool_id: PoolId) -> PoolBalance {
+        let pool = self.pool_metadata_by_id.get(&pool_id).expect("Staking pool doesn't exist");
+
+        PoolBalance {
+            total_reward: U128(self.internal_pool_total_reward(&pool.pool_info)),
+            funded: U128(pool.total_reward_funded),
+            distributed: U128(pool.total_reward_distributed),
+            withdrawn: U128(pool.total_reward_withdrawn),
+            remaining: U128(self.internal_pool_remaining(&pool)),
+        }
+    }
+
+    fn check_pool_eligibility(&self, pet_id: PetId, pool_id: PoolId) -> PoolEligibilityCheck {
+        let pool = self.pool_metadata_by_id.get(&pool_id).expect("Staking pool doesn't exist");
+        let pet = self.pet_metadata_by_id.get(&pet_id).expect("Pet doesn't exist");
+
+        let reasons = self.internal_pool_ineligibility_reasons(&pool.pool_info, &pet);
+
+        PoolEligibilityCheck {
+            eligible: reasons.is_empty(),
+            reasons,
+        }
+    }
+
     fn get_mining_data_by_account_id(&self, account_id: AccountId) -> MiningData {
-        let mining_data = self.mining_data_by_account_id.get(&account_id).unwrap();
+        let mut mining_data = self.mining_data_by_account_id.get(&account_id).unwrap();
+        self.internal_sync_mining_season(&mut mining_data);
 
         mining_data
     }
+
+    fn preview_mining(&self, account_id: AccountId) -> MiningPreview {
+        let mining_data = self.mining_data_by_account_id.get(&account_id).expect("Mining tool should not be empty");
+        let ready_at = mining_data.last_mining_time + mining_data.total_mining_charge_time;
+
+        MiningPreview {
+            mining_points: self.internal_mining_points(&mining_data),
+            charge_time: mining_data.total_mining_charge_time,
+            ready_at,
+            is_ready: self.internal_mining_working_power(&mining_data) > 0 && env::block_timestamp() as u128 >= ready_at,
+        }
+    }
+
+    fn get_redemption_items(&self) -> Vec<ItemId> {
+        self.redemption_item_ids.clone()
+    }
+
+    fn get_mining_difficulty(&self) -> MiningDifficulty {
+        self.mining_difficulty.clone()
+    }
+
+    fn get_mining_share(&self, account_id: AccountId) -> MiningShare {
+        let network_mining_power = self.mining_difficulty.network_mining_power;
+        let mining_data = match self.mining_data_by_account_id.get(&account_id) {
+            Some(mining_data) => mining_data,
+            None => {
+                return MiningShare {
+                    mining_power: 0,
+                    network_mining_power,
+                    share_bps: 0,
+                    estimated_points_per_epoch: 0,
+                }
+            }
+        };
+
+        let share_bps = (mining_data.total_mining_power * BASIS_POINTS)
+            .checked_div(network_mining_power)
+            .unwrap_or(0);
+
+        // Mining every time the tools are charged during a whole epoch
+        let estimated_points_per_epoch = (self.internal_mining_points(&mining_data) * self.mining_difficulty.epoch_duration)
+            .checked_div(mining_data.total_mining_charge_time)
+            .unwrap_or(0);
+
+        MiningShare {
+            mining_power: mining_data.total_mining_power,
+            network_mining_power,
+            share_bps,
+            estimated_points_per_epoch,
+        }
+    }
 }
@@ -0,0 +1,29 @@
+use near_sdk::{near_bindgen, AccountId};
+
+use crate::models::{
+    contract::{JoychiV1, JoychiV1Ext},
+    mining_season::{MiningSeason, MiningSeasonsEnum, SeasonRank},
+    SeasonId,
+};
+
+#[near_bindgen]
+impl MiningSeasonsEnum for JoychiV1 {
+    fn get_mining_season(&self, season_id: SeasonId) -> Option<MiningSeason> {
+        self.mining_season_by_id.get(&season_id)
+    }
+
+    fn get_current_mining_season(&self) -> Option<MiningSeason> {
+        self.internal_active_season()
+    }
+
+    fn get_season_leaderboard(&self, season_id: SeasonId) -> Vec<SeasonRank> {
+        self.mining_season_by_id.get(&season_id).expect("Mining season doesn't exist").leaderboard
+    }
+
+    fn get_season_points(&self, season_id: SeasonId, account_id: AccountId) -> u128 {
+        self.season_points_by_id
+            .get(&season_id)
+            .and_then(|season_points| season_points.get(&account_id))
+            .unwrap_or(0)
+    }
+}
@@ -1,10 +1,10 @@
-use near_sdk::{env, near_bindgen};
+use near_sdk::{env, near_bindgen, AccountId};
 
 use crate::{
     application::repository::HOUR,
     models::{
         contract::{BattleMetadata, JoychiV1, JoychiV1Ext, Status},
-        pet::{PetEnum, PetEvolution, PetMetadata},
+        pet::{PetEnum, PetEvolution, PetMetadata, PetOwnerStats},
         BattleId, PetId,
     },
 };
@@ -106,4 +106,12 @@ impl PetEnum for JoychiV1 {
         }
         return current_evo_phase;
     }
+
+    fn get_pet_owner_stats(&self, account_id: AccountId) -> PetOwnerStats {
+        self.pet_owner_stats_by_account.get(&account_id).unwrap_or_default()
+    }
+
+    fn get_marketplaces(&self) -> Vec<AccountId> {
+        self.marketplace_ids.clone()
+    }
 }
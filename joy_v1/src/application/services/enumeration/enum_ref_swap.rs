@@ -0,0 +1,13 @@
+use near_sdk::near_bindgen;
+
+use crate::models::{
+    contract::{JoychiV1, JoychiV1Ext},
+    ref_swap::{RefExchange, RefSwapEnum},
+};
+
+#[near_bindgen]
+impl RefSwapEnum for JoychiV1 {
+    fn get_ref_exchange(&self) -> Option<RefExchange> {
+        self.ref_exchange.clone()
+    }
+}
@@ -0,0 +1,14 @@
+use near_sdk::near_bindgen;
+
+use crate::models::{
+    contract::{JoychiV1, JoychiV1Ext},
+    rental::{RentalEnum, RentalOffer},
+    PetId,
+};
+
+#[near_bindgen]
+impl RentalEnum for JoychiV1 {
+    fn get_rental_by_pet_id(&self, pet_id: PetId) -> Option<RentalOffer> {
+        self.rental_by_pet_id.get(&pet_id)
+    }
+}
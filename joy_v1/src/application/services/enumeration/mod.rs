@@ -2,3 +2,6 @@ pub mod enum_item_immidiate;
 pub mod enum_pet;
 pub mod enum_item_factory;
 pub mod enum_mining;
+pub mod enum_rental;
+pub mod enum_mining_season;
+pub mod enum_ref_swap;
\ No newline at end of file
@@ -0,0 +1,37 @@
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, PromiseOrValue};
+
+use crate::models::{
+    contract::{JoychiV1, JoychiV1Ext},
+    ft_request::receiver::FtOnTransferMsg,
+};
+
+#[near_bindgen]
+impl FungibleTokenReceiver for JoychiV1 {
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        assert!(
+            env::predecessor_account_id() == self.ft_address,
+            "Only JOY is accepted"
+        );
+
+        let msg: FtOnTransferMsg = near_sdk::serde_json::from_str(&msg).expect("Invalid msg");
+
+        match msg {
+            FtOnTransferMsg::RentPet { pet_id } => self.internal_rent_pet(sender_id, pet_id, amount),
+            FtOnTransferMsg::FundPool { pool_id } => {
+                PromiseOrValue::Value(self.internal_fund_pool(sender_id, pool_id, amount))
+            }
+            FtOnTransferMsg::RepairTool { token_id } => {
+                PromiseOrValue::Value(self.internal_repair_tool(token_id, amount))
+            }
+            FtOnTransferMsg::FundSeason { season_id } => {
+                PromiseOrValue::Value(self.internal_fund_season(sender_id, season_id, amount))
+            }
+        }
+    }
+}
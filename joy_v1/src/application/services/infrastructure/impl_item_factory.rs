@@ -1,8 +1,9 @@
-use near_sdk::{env, near_bindgen, AccountId};
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Promise};
 
 use crate::models::{
     contract::{JoychiV1, JoychiV1Ext},
-    item_factory::{ItemFeature, ItemMetadata, ItemRarity, ItemType},
+    item_factory::{ItemFeature, ItemInstance, ItemMetadata, ItemRarity, ItemType},
     nft_request::external::{cross_item_nft, TokenMetadata},
     ItemId,
 };
@@ -21,11 +22,16 @@ impl ItemFeature for JoychiV1 {
         prototype_item_rarity: ItemRarity,
         prototype_itemmining_power: u128,
         prototype_itemmining_charge_time: u128,
+        prototype_item_max_durability: u128,
+        prototype_item_repair_price: u128,
     ) -> ItemMetadata {
         assert!(
             self.owner_id == env::signer_account_id(),
             "You're not permission"
         );
+        if prototype_item_type == ItemType::MineTool {
+            assert!(prototype_item_max_durability > 0, "Mining tool durability must be greater than 0");
+        }
         let num_item_id = self.all_item_id.len();
 
         let item_metadata = ItemMetadata {
@@ -40,8 +46,9 @@ impl ItemFeature for JoychiV1 {
             prototype_item_rarity,
             prototype_itemmining_power,
             prototype_itemmining_charge_time,
-            owner: env::signer_account_id(),
-            is_lock: false,
+            prototype_item_max_durability,
+            prototype_item_repair_price,
+            total_supply: 0,
         };
 
         self.item_metadata_by_id
@@ -63,6 +70,8 @@ impl ItemFeature for JoychiV1 {
         prototype_item_rarity: ItemRarity,
         prototype_itemmining_power: u128,
         prototype_itemmining_charge_time: u128,
+        prototype_item_max_durability: u128,
+        prototype_item_repair_price: u128,
     ) {
         let mut item: ItemMetadata = self.item_metadata_by_id.get(&item_id).unwrap();
 
@@ -70,6 +79,9 @@ impl ItemFeature for JoychiV1 {
             self.owner_id == env::signer_account_id(),
             "You're not permission"
         );
+        if item.prototype_item_type == ItemType::MineTool {
+            assert!(prototype_item_max_durability > 0, "Mining tool durability must be greater than 0");
+        }
 
         item.prototype_item_image = prototype_item_image;
         item.prototype_item_cooldown_breed_time = prototype_item_cooldown_breed_time;
@@ -78,19 +90,75 @@ impl ItemFeature for JoychiV1 {
         item.prototype_item_rarity = prototype_item_rarity;
         item.prototype_itemmining_power = prototype_itemmining_power;
         item.prototype_itemmining_charge_time = prototype_itemmining_charge_time;
+        item.prototype_item_max_durability = prototype_item_max_durability;
+        item.prototype_item_repair_price = prototype_item_repair_price;
 
         self.item_metadata_by_id.insert(&item_id, &item);
 
         // TODO
     }
 
-    fn mint_item_for_user(&mut self, to_addr: AccountId, item_id: ItemId) {
-        let mut item_metadata = self.item_metadata_by_id.get(&item_id).unwrap();
+    fn mint_item_for_user(&mut self, to_addr: AccountId, item_id: ItemId) -> TokenId {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+
+        self.internal_mint_item(to_addr, item_id)
+    }
+}
+
+impl JoychiV1 {
+    // Mint a new instance of the item prototype, every instance gets its own NFT
+    pub fn internal_mint_item(&mut self, to_addr: AccountId, item_id: ItemId) -> TokenId {
+        let (token_id, _) = self.internal_mint_item_with_promise(to_addr, item_id);
+
+        token_id
+    }
+
+    // Same as internal_mint_item, the promise of the mint lets the caller handle its result
+    pub fn internal_mint_item_with_promise(&mut self, to_addr: AccountId, item_id: ItemId) -> (TokenId, Promise) {
+        let mut item_metadata = self.item_metadata_by_id.get(&item_id).expect("Item doesn't exist");
+
+        item_metadata.total_supply += 1;
+        // A failed mint gives its place in the supply back, so skip the serials still in use after it
+        let mut serial = item_metadata.total_supply;
+        while self.item_instance_by_token_id.contains_key(&format!("{}:{}", item_id, serial)) {
+            serial += 1;
+        }
+        let token_id: TokenId = format!("{}:{}", item_id, serial);
+
+        let item_instance = ItemInstance {
+            token_id: token_id.clone(),
+            item_id,
+            serial,
+            is_lock: false,
+            durability: item_metadata.prototype_item_max_durability,
+            minted_at: env::block_timestamp(),
+        };
+
+        let promise = cross_item_nft::ext(self.nft_item_address.to_owned())
+        .with_static_gas(GAS_FOR_CROSS_CALL)
+        .with_attached_deposit(ATTACHED_DEPOSIT_NFT)
+        .nft_mint(
+            token_id.clone(),
+            self.internal_item_token_metadata(&item_metadata, &item_instance),
+            to_addr,
+            Some(item_metadata.prototype_item_type.token_type()),
+        );
 
-        let token_metadata = TokenMetadata {
+        self.item_instance_by_token_id.insert(&token_id, &item_instance);
+        self.item_metadata_by_id.insert(&item_id, &item_metadata);
+
+        (token_id, promise)
+    }
+
+    // The NFT metadata of an item instance, shows the prototype and the durability left
+    pub fn internal_item_token_metadata(&self, item_metadata: &ItemMetadata, item_instance: &ItemInstance) -> TokenMetadata {
+        TokenMetadata {
             title: Some(item_metadata.prototype_item_image.clone()),
             description: Some(format!(
-                "item_image:{}, item_type:{:?}, cooldown_breed_time:{}, reduce_breed_fee:{}, item_points:{:?}, item_rarity:{:?}, mining_power:{}, mining_charge_time:{}",
+                "item_image:{}, item_type:{:?}, cooldown_breed_time:{}, reduce_breed_fee:{}, item_points:{:?}, item_rarity:{:?}, mining_power:{}, mining_charge_time:{}, durability:{}/{}",
                 item_metadata.prototype_item_image,
                 item_metadata.prototype_item_type,
                 item_metadata.prototype_item_cooldown_breed_time,
@@ -98,32 +166,57 @@ impl ItemFeature for JoychiV1 {
                 item_metadata.prototype_item_points,
                 item_metadata.prototype_item_rarity,
                 item_metadata.prototype_itemmining_power,
-                item_metadata.prototype_itemmining_charge_time
+                item_metadata.prototype_itemmining_charge_time,
+                item_instance.durability,
+                item_metadata.prototype_item_max_durability
         )),
             media: Some(item_metadata.prototype_item_image.clone()),
             media_hash: None,
-            copies: None,
+            copies: Some(item_instance.serial),
             issued_at: None,
-            expires_at: Some(env::block_timestamp()),
-            starts_at: Some(env::block_timestamp()),
+            expires_at: None,
+            starts_at: Some(item_instance.minted_at),
             updated_at: Some(env::block_timestamp()),
-            extra: None,
+            extra: Some(format!(
+                "{{\"durability\":{},\"max_durability\":{}}}",
+                item_instance.durability, item_metadata.prototype_item_max_durability
+            )),
             reference: None,
             reference_hash: None,
-        };
+        }
+    }
+
+    // Push the durability of a tool to its NFT metadata
+    pub fn internal_sync_item_token_metadata(&self, item_instance: &ItemInstance) {
+        let item_metadata = self.item_metadata_by_id.get(&item_instance.item_id).unwrap();
 
         cross_item_nft::ext(self.nft_item_address.to_owned())
-        .with_static_gas(GAS_FOR_CROSS_CALL)
-        .with_attached_deposit(ATTACHED_DEPOSIT_NFT)
-        .nft_mint(
-            (item_id.clone()).to_string(),
-            token_metadata,
-            to_addr.clone(),
-        );
+            .with_static_gas(GAS_FOR_CROSS_CALL)
+            .update_token_metadata(
+                item_instance.token_id.clone(),
+                self.internal_item_token_metadata(&item_metadata, item_instance),
+            );
+    }
 
-        item_metadata.owner = to_addr;
-        self.item_metadata_by_id.insert(&item_id, &item_metadata);
+    // Restore the durability of a tool with the JOY sent, the JOY that isn't needed is refunded
+    pub fn internal_repair_tool(&mut self, token_id: TokenId, amount: U128) -> U128 {
+        let mut item_instance = self.item_instance_by_token_id.get(&token_id).expect("Item instance doesn't exist");
+        let item_metadata = self.item_metadata_by_id.get(&item_instance.item_id).unwrap();
 
-    }
+        assert!(item_metadata.prototype_item_type == ItemType::MineTool, "This item is not a mining tool");
+        let missing = item_metadata.prototype_item_max_durability.saturating_sub(item_instance.durability);
+        assert!(missing > 0, "This tool doesn't need to be repaired");
+
+        // A tool without repair price is repaired for free
+        let repaired = amount.0
+            .checked_div(item_metadata.prototype_item_repair_price)
+            .map_or(missing, |points| std::cmp::min(missing, points));
+        assert!(repaired > 0, "Not enough JOY to repair this tool");
 
+        item_instance.durability += repaired;
+        self.item_instance_by_token_id.insert(&token_id, &item_instance);
+        self.internal_sync_item_token_metadata(&item_instance);
+
+        U128(amount.0 - repaired * item_metadata.prototype_item_repair_price)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,185 @@
+use near_sdk::{collections::LookupMap, env, json_types::U128, near_bindgen, AccountId};
+
+use crate::models::{
+    contract::{JoychiV1, JoychiV1Ext, JoychiV1StorageKey},
+    ft_request::external::cross_ft,
+    mining_season::{MiningSeason, MiningSeasons, SeasonRank},
+    staking_and_mining::MiningData,
+    SeasonId,
+};
+
+use super::impl_pet::GAS_FOR_CROSS_CALL;
+use super::impl_staking_and_mining::{ATTACHED_TRANSFER_FT, BASIS_POINTS};
+
+#[near_bindgen]
+impl MiningSeasons for JoychiV1 {
+    fn create_mining_season(
+        &mut self,
+        start_time: u128,
+        end_time: u128,
+        rank_rewards: Vec<U128>,
+        carry_over_bps: u128,
+        halving: bool,
+    ) -> MiningSeason {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+        assert!(end_time > start_time, "Season must end after it starts");
+        assert!(carry_over_bps <= BASIS_POINTS, "Carry over can't be more than 100%");
+
+        let previous = self.mining_season_by_id.get(&self.mining_season_count);
+        let emission_bps = match &previous {
+            Some(previous) => {
+                assert!(start_time >= previous.end_time, "Season must start after the previous one ends");
+                if halving {
+                    previous.emission_bps / 2
+                } else {
+                    previous.emission_bps
+                }
+            }
+            None => BASIS_POINTS,
+        };
+
+        self.mining_season_count += 1;
+        let season = MiningSeason {
+            season_id: self.mining_season_count,
+            start_time,
+            end_time,
+            emission_bps,
+            carry_over_bps,
+            rank_rewards,
+            total_reward_funded: 0,
+            total_reward_withdrawn: 0,
+            leaderboard: Vec::new(),
+        };
+
+        self.mining_season_by_id.insert(&season.season_id, &season);
+
+        season
+    }
+
+    fn claim_season_reward(&mut self, season_id: SeasonId) -> U128 {
+        let mut season = self.mining_season_by_id.get(&season_id).expect("Mining season doesn't exist");
+        let account_id = env::signer_account_id();
+
+        assert!(env::block_timestamp() as u128 >= season.end_time, "Mining season is not over");
+        assert!(season.total_reward_funded == self.internal_season_total_reward(&season), "Mining season is not funded");
+
+        let rank = season.leaderboard.iter()
+            .position(|entry| entry.account_id == account_id)
+            .expect("You are not in the leaderboard");
+        assert!(!season.leaderboard[rank].claimed, "Reward already claimed");
+
+        season.leaderboard[rank].claimed = true;
+        self.mining_season_by_id.insert(&season_id, &season);
+
+        let reward = season.rank_rewards[rank];
+        cross_ft::ext(self.ft_address.to_owned())
+            .with_static_gas(GAS_FOR_CROSS_CALL)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .ft_transfer(account_id, reward, None);
+
+        reward
+    }
+
+    fn owner_withdraw_unclaimed_season_reward(&mut self, season_id: SeasonId) -> U128 {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+        let mut season = self.mining_season_by_id.get(&season_id).expect("Mining season doesn't exist");
+        assert!(env::block_timestamp() as u128 >= season.end_time, "Mining season is not over");
+
+        // The ranks of the leaderboard keep their reward, claimed or not
+        let ranked_reward: u128 = season.rank_rewards.iter()
+            .take(season.leaderboard.len())
+            .map(|reward| reward.0)
+            .sum();
+        let unclaimed = season.total_reward_funded
+            .saturating_sub(ranked_reward)
+            .saturating_sub(season.total_reward_withdrawn);
+        assert!(unclaimed > 0, "No unclaimed reward to withdraw");
+
+        season.total_reward_withdrawn += unclaimed;
+        self.mining_season_by_id.insert(&season_id, &season);
+
+        cross_ft::ext(self.ft_address.to_owned())
+            .with_static_gas(GAS_FOR_CROSS_CALL)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .ft_transfer(self.owner_id.clone(), U128(unclaimed), None);
+
+        U128(unclaimed)
+    }
+}
+
+impl JoychiV1 {
+    // The season accounts can mine in right now
+    pub fn internal_active_season(&self) -> Option<MiningSeason> {
+        let now = env::block_timestamp() as u128;
+
+        (1..=self.mining_season_count).rev()
+            .map(|season_id| self.mining_season_by_id.get(&season_id).unwrap())
+            .find(|season| season.start_time <= now)
+            .filter(|season| now < season.end_time)
+    }
+
+    pub fn internal_season_total_reward(&self, season: &MiningSeason) -> u128 {
+        season.rank_rewards.iter().map(|reward| reward.0).sum()
+    }
+
+    // Apply the carry over of every season that ended since the account last mined
+    pub fn internal_sync_mining_season(&self, mining_data: &mut MiningData) {
+        let now = env::block_timestamp() as u128;
+
+        while mining_data.settled_season_id < self.mining_season_count {
+            let season = self.mining_season_by_id.get(&(mining_data.settled_season_id + 1)).unwrap();
+            if now < season.end_time {
+                break;
+            }
+
+            mining_data.mining_points = mining_data.mining_points * season.carry_over_bps / BASIS_POINTS;
+            mining_data.settled_season_id = season.season_id;
+        }
+    }
+
+    // Count the points mined in the season for the account and keep the top miners in the leaderboard
+    pub fn internal_record_season_points(&mut self, season: &mut MiningSeason, mining_data: &mut MiningData, account_id: AccountId, points: u128) {
+        if mining_data.season_id != season.season_id {
+            mining_data.season_id = season.season_id;
+            mining_data.season_points = 0;
+        }
+        mining_data.season_points += points;
+
+        let mut season_points = self.season_points_by_id.get(&season.season_id).unwrap_or_else(|| {
+            LookupMap::new(JoychiV1StorageKey::SeasonPointsInner { season_id: season.season_id })
+        });
+        season_points.insert(&account_id, &mining_data.season_points);
+        self.season_points_by_id.insert(&season.season_id, &season_points);
+
+        season.leaderboard.retain(|entry| entry.account_id != account_id);
+        season.leaderboard.push(SeasonRank {
+            account_id,
+            points: mining_data.season_points,
+            claimed: false,
+        });
+        season.leaderboard.sort_by_key(|entry| std::cmp::Reverse(entry.points));
+        season.leaderboard.truncate(season.rank_rewards.len());
+
+        self.mining_season_by_id.insert(&season.season_id, season);
+    }
+
+    pub fn internal_fund_season(&mut self, sender_id: AccountId, season_id: SeasonId, amount: U128) -> U128 {
+        assert!(self.owner_id == sender_id, "You're not permission");
+        let mut season = self.mining_season_by_id.get(&season_id).expect("Mining season doesn't exist");
+
+        let missing = self.internal_season_total_reward(&season) - season.total_reward_funded;
+        assert!(missing > 0, "Mining season is already funded");
+
+        let funded = amount.0.min(missing);
+        season.total_reward_funded += funded;
+        self.mining_season_by_id.insert(&season_id, &season);
+
+        U128(amount.0 - funded)
+    }
+}
@@ -0,0 +1,40 @@
+use near_contract_standards::non_fungible_token::{core::NonFungibleTokenReceiver, TokenId};
+use near_sdk::{env, near_bindgen, AccountId, PromiseOrValue};
+
+use crate::models::{
+    contract::{JoychiV1, JoychiV1Ext},
+    nft_request::receiver::NftOnTransferMsg,
+    PetId,
+};
+
+#[near_bindgen]
+impl NonFungibleTokenReceiver for JoychiV1 {
+    // The NFT is kept by the contract until it's unstaked or removed from mining,
+    // if anything fails the NFT contract gives it back to the previous owner
+    fn nft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: TokenId,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        assert!(sender_id == previous_owner_id, "Only the owner can send the NFT");
+
+        let nft_contract_id = env::predecessor_account_id();
+        let msg: NftOnTransferMsg = near_sdk::serde_json::from_str(&msg).expect("Invalid msg");
+
+        match msg {
+            NftOnTransferMsg::Stake { pool_id, lock_term } => {
+                assert!(nft_contract_id == self.nft_address, "Only pets can be staked");
+                let pet_id: PetId = token_id.parse().expect("Invalid pet id");
+                self.internal_stake(previous_owner_id, pet_id, pool_id, lock_term);
+            }
+            NftOnTransferMsg::AddMiningTool => {
+                assert!(nft_contract_id == self.nft_item_address, "Only items can be used for mining");
+                self.internal_add_mining_tool(previous_owner_id, token_id);
+            }
+        }
+
+        PromiseOrValue::Value(false)
+    }
+}
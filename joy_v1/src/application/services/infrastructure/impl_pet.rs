@@ -1,7 +1,7 @@
 use near_sdk::{
     env::{self},
     json_types::U128,
-    near_bindgen, AccountId, Gas, Promise,
+    near_bindgen, AccountId, Gas, Promise, PromiseError,
 };
 
 use crate::{
@@ -9,8 +9,9 @@ use crate::{
     models::{
         contract::{BattleMetadata, JoychiV1, JoychiV1Ext, Status},
         ft_request::external::cross_ft,
-        nft_request::external::{cross_pet_nft, PetAttribute, TokenMetadata},
+        nft_request::external::{cross_pet_nft, JsonToken, PetAttribute, TokenMetadata},
         pet::{PetEnum, PetEvolution, PetFeature, PetMetadata, PetSpecies},
+        rental::Rental,
         ItemId, PetId,
     },
 };
@@ -20,6 +21,9 @@ pub const ATTACHED_DEPOSIT_NFT: u128 = 100_000_000_000_000_000_000_000;
 pub const ATTACHED_BURN_FT: u128 = 1_000_000_000_000;
 pub const PRECISION: u128 = 1e24 as u128;
 pub const BURN_AMOUNT: U128 = U128(10000000000);
+pub const GAS_FOR_RESOLVE_SYNC_PET_OWNER: Gas = Gas(5_000_000_000_000);
+
+use super::impl_staking_and_mining::GAS_FOR_NFT_TOKEN;
 
 #[near_bindgen]
 impl PetFeature for JoychiV1 {
@@ -31,6 +35,14 @@ impl PetFeature for JoychiV1 {
         self.manager_address = manager_addr;
     }
 
+    fn set_marketplaces(&mut self, marketplace_ids: Vec<AccountId>) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+        self.marketplace_ids = marketplace_ids;
+    }
+
     fn token_uri(&mut self, pet_id: PetId) -> PetAttribute {
         let pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
         let evol_phase_pet_now = self.get_pet_evolution_phase(pet_id, pet.pet_evolution_phase);
@@ -145,6 +157,11 @@ impl PetFeature for JoychiV1 {
         self.pet_metadata_by_id.insert(&pet_id, &pet_metadata);
         self.all_pet_id.insert(&pet_id);
 
+        let mut owner_stats = self.pet_owner_stats_by_account.get(&owner_id).unwrap_or_default();
+        owner_stats.pet_count += 1;
+        owner_stats.pets_created += 1;
+        self.pet_owner_stats_by_account.insert(&owner_id, &owner_stats);
+
         cross_pet_nft::ext(self.nft_address.to_owned())
             .with_static_gas(GAS_FOR_CROSS_CALL)
             .with_attached_deposit(ATTACHED_DEPOSIT_NFT)
@@ -174,47 +191,15 @@ impl PetFeature for JoychiV1 {
     }
 
     fn buy_item_immidiate(&mut self, pet_id: PetId, item_id: ItemId) {
-        let mut pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
-
-        let mut item = self.item_immidiate_metadata_by_id.get(&item_id).unwrap();
-
-        assert!(
-            pet.owner_id == env::signer_account_id(),
-            "You're not permission"
-        );
-        assert!(
-            self.is_pet_alive(pet_id) || (!self.is_pet_alive(pet_id) && item.is_revival),
-            "Pet's not alive"
-        );
-        assert!(item.name.len() > 0, "This item doesn't exist");
-
-        if pet.pet_need_evolution_item && pet.pet_evolution_item_id == item_id as u128 {
-            pet.pet_has_evolution_item = true;
+        if let Some(error) = self.internal_item_immidiate_purchase_error(pet_id, item_id, &env::signer_account_id()) {
+            env::panic_str(error);
         }
 
-        pet.items.push(item.clone());
-
-        self.total_score += item.points;
-
-        pet.score += item.points;
-        pet.pet_shield += item.shield;
-
-        let time_extension = env::block_timestamp() as u128 + item.time_extension;
-        pet.time_until_starving = time_extension;
-
-        pet.status = update_status_pet(time_extension);
-        
-        // calc petRewardDebt
-
-        item.price += item.price_delta;
-        item.stock -= 1;
-
-        self.item_immidiate_metadata_by_id.insert(&item_id, &item);
-        self.pet_metadata_by_id.insert(&pet_id, &pet);
+        let price = self.internal_feed_item_immidiate(pet_id, item_id);
 
         cross_ft::ext(self.ft_address.to_owned())
             .with_static_gas(GAS_FOR_CROSS_CALL)
-            .ft_burn(pet.owner_id.clone(), U128(item.price));
+            .ft_burn(env::signer_account_id(), U128(price));
     }
 
     fn attack(&mut self, from_id: PetId, to_id: PetId) -> BattleMetadata {
@@ -226,8 +211,9 @@ impl PetFeature for JoychiV1 {
         let mut pet_from = self.pet_metadata_by_id.get(&from_id).unwrap();
         let mut pet_to = self.pet_metadata_by_id.get(&to_id).unwrap();
 
+        // The renter attacks with the pet during a rental
         assert!(
-            pet_from.owner_id == env::signer_account_id(),
+            self.check_role_play_pet(from_id, env::signer_account_id()),
             "You're not permission"
         );
 
@@ -318,6 +304,9 @@ impl PetFeature for JoychiV1 {
             self.pet_metadata_by_id.get(&pet_kill).unwrap().owner_id,
         );
 
+        let killed_owner_id = self.pet_metadata_by_id.get(&pet_kill).unwrap().owner_id;
+        self.internal_move_pet_count(Some(&killed_owner_id), None);
+
         // Remove the pet_id from the Vec
         self.all_pet_id.remove(&pet_kill);
 
@@ -441,6 +430,128 @@ impl PetFeature for JoychiV1 {
 
         Promise::new(to_addr.clone()).transfer(1 / 10_000);
     }
+
+    fn sync_pet_owner(&mut self, pet_id: PetId) {
+        assert!(self.pet_metadata_by_id.get(&pet_id).is_some(), "Pet doesn't exist");
+
+        // The pet NFT contract is the source of truth for the owner, so anyone can trigger the sync
+        cross_pet_nft::ext(self.nft_address.to_owned())
+            .with_static_gas(GAS_FOR_NFT_TOKEN)
+            .nft_token(pet_id.to_string())
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_SYNC_PET_OWNER)
+                    .resolve_sync_pet_owner(pet_id),
+            );
+    }
+}
+
+#[near_bindgen]
+impl JoychiV1 {
+    #[private]
+    pub fn resolve_sync_pet_owner(
+        &mut self,
+        pet_id: PetId,
+        #[callback_result] token: Result<Option<JsonToken>, PromiseError>,
+    ) {
+        let token = token.ok().flatten().expect("Pet NFT doesn't exist");
+        let mut pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
+
+        // A staked pet is held by the game, its owner stays the staker
+        if token.owner_id == env::current_account_id() {
+            return;
+        }
+
+        // A pet escrowed in a marketplace auction keeps the seller as owner until it's settled or returned
+        if self.marketplace_ids.contains(&token.owner_id) {
+            return;
+        }
+
+        self.internal_move_pet_count(Some(&pet.owner_id), Some(&token.owner_id));
+        pet.owner_id = token.owner_id;
+
+        self.pet_metadata_by_id.insert(&pet_id, &pet);
+    }
+}
+
+impl JoychiV1 {
+    // Why the account can't buy the item for the pet, None if it can
+    pub(crate) fn internal_item_immidiate_purchase_error(
+        &self,
+        pet_id: PetId,
+        item_id: ItemId,
+        account_id: &AccountId,
+    ) -> Option<&'static str> {
+        if !self.pet_metadata_by_id.contains_key(&pet_id) {
+            return Some("Pet doesn't exist");
+        }
+        let item = match self.item_immidiate_metadata_by_id.get(&item_id) {
+            Some(item) if !item.name.is_empty() => item,
+            _ => return Some("This item doesn't exist"),
+        };
+
+        // The renter feeds the pet during a rental
+        if !self.check_role_play_pet(pet_id, account_id.clone()) {
+            return Some("You're not permission");
+        }
+        if !self.is_pet_alive(pet_id) && !item.is_revival {
+            return Some("Pet's not alive");
+        }
+        if item.stock == 0 {
+            return Some("This item is out of stock");
+        }
+
+        None
+    }
+
+    // Feed the item to the pet, returns the JOY to burn: the price raised by the purchase
+    pub(crate) fn internal_feed_item_immidiate(&mut self, pet_id: PetId, item_id: ItemId) -> u128 {
+        let mut pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
+        let mut item = self.item_immidiate_metadata_by_id.get(&item_id).unwrap();
+
+        if pet.pet_need_evolution_item && pet.pet_evolution_item_id == item_id as u128 {
+            pet.pet_has_evolution_item = true;
+        }
+
+        pet.items.push(item.clone());
+
+        self.total_score += item.points;
+
+        pet.score += item.points;
+        pet.pet_shield += item.shield;
+
+        let time_extension = env::block_timestamp() as u128 + item.time_extension;
+        pet.time_until_starving = time_extension;
+
+        pet.status = update_status_pet(time_extension);
+
+        item.price += item.price_delta;
+        item.stock -= 1;
+
+        self.item_immidiate_metadata_by_id.insert(&item_id, &item);
+        self.pet_metadata_by_id.insert(&pet_id, &pet);
+
+        item.price
+    }
+
+    // Move a pet between the counts of two owners, None when the pet is created or killed
+    pub(crate) fn internal_move_pet_count(&mut self, from: Option<&AccountId>, to: Option<&AccountId>) {
+        if from == to {
+            return;
+        }
+
+        if let Some(from) = from {
+            let mut owner_stats = self.pet_owner_stats_by_account.get(from).unwrap_or_default();
+            owner_stats.pet_count = owner_stats.pet_count.saturating_sub(1);
+            self.pet_owner_stats_by_account.insert(from, &owner_stats);
+        }
+
+        if let Some(to) = to {
+            let mut owner_stats = self.pet_owner_stats_by_account.get(to).unwrap_or_default();
+            owner_stats.pet_count += 1;
+            self.pet_owner_stats_by_account.insert(to, &owner_stats);
+        }
+    }
 }
 
 
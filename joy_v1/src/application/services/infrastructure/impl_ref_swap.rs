@@ -0,0 +1,244 @@
+use near_sdk::{env, json_types::U128, log, near_bindgen, AccountId, Gas, Promise, PromiseError, PromiseResult};
+
+use crate::models::{
+    contract::{JoychiV1, JoychiV1Ext},
+    ft_request::external::cross_ft,
+    ref_request::external::{cross_ref_exchange, cross_wnear, SwapAction},
+    ref_swap::{RefExchange, RefSwap},
+    ItemId, PetId,
+};
+
+use super::impl_pet::GAS_FOR_CROSS_CALL;
+use super::impl_staking_and_mining::ATTACHED_TRANSFER_FT;
+
+pub const GAS_FOR_NEAR_DEPOSIT: Gas = Gas(5_000_000_000_000);
+pub const GAS_FOR_REF_DEPOSIT: Gas = Gas(40_000_000_000_000);
+pub const GAS_FOR_REF_SWAP: Gas = Gas(15_000_000_000_000);
+pub const GAS_FOR_REF_WITHDRAW: Gas = Gas(55_000_000_000_000);
+pub const GAS_FOR_RESOLVE_BUY_WITH_NEAR: Gas = Gas(100_000_000_000_000);
+pub const GAS_FOR_RESOLVE_NEAR_DEPOSIT: Gas = Gas(175_000_000_000_000);
+pub const GAS_FOR_RESOLVE_REF_DEPOSIT: Gas = Gas(125_000_000_000_000);
+pub const GAS_FOR_RESOLVE_REF_WITHDRAW: Gas = Gas(20_000_000_000_000);
+pub const GAS_FOR_RESOLVE_NEAR_WITHDRAW: Gas = Gas(5_000_000_000_000);
+
+#[near_bindgen]
+impl RefSwap for JoychiV1 {
+    fn set_ref_exchange(&mut self, ref_exchange: Option<RefExchange>) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+        self.ref_exchange = ref_exchange;
+    }
+
+    #[payable]
+    fn buy_item_immidiate_with_near(&mut self, pet_id: PetId, item_id: ItemId) -> Promise {
+        let ref_exchange = self.ref_exchange.clone().expect("NEAR purchases are off");
+        let account_id = env::signer_account_id();
+        if let Some(error) = self.internal_item_immidiate_purchase_error(pet_id, item_id, &account_id) {
+            env::panic_str(error);
+        }
+
+        let amount_in = env::attached_deposit();
+        assert!(amount_in > 0, "Attach the NEAR to pay with");
+
+        // The swap must give at least the JOY burned by the purchase
+        let item = self.item_immidiate_metadata_by_id.get(&item_id).unwrap();
+        let min_amount_out = U128(item.price + item.price_delta);
+
+        cross_wnear::ext(ref_exchange.wnear_id)
+            .with_static_gas(GAS_FOR_NEAR_DEPOSIT)
+            .with_attached_deposit(amount_in)
+            .near_deposit()
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_NEAR_DEPOSIT)
+                    .resolve_near_deposit(account_id, pet_id, item_id, U128(amount_in), min_amount_out),
+            )
+    }
+}
+
+#[near_bindgen]
+impl JoychiV1 {
+    // Send the wNEAR to Ref if the NEAR was wrapped, otherwise refund the buyer
+    #[private]
+    pub fn resolve_near_deposit(
+        &mut self,
+        account_id: AccountId,
+        pet_id: PetId,
+        item_id: ItemId,
+        amount_in: U128,
+        min_amount_out: U128,
+    ) -> bool {
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            log!("The {} NEAR of {} couldn't be wrapped", amount_in.0, account_id);
+            Promise::new(account_id).transfer(amount_in.0);
+            return false;
+        }
+
+        let ref_exchange = self.ref_exchange.clone().expect("NEAR purchases are off");
+        cross_wnear::ext(ref_exchange.wnear_id)
+            .with_static_gas(GAS_FOR_REF_DEPOSIT)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .ft_transfer_call(ref_exchange.ref_exchange_id, amount_in, None, String::new())
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_REF_DEPOSIT)
+                    .resolve_ref_deposit(account_id, pet_id, item_id, amount_in, min_amount_out),
+            );
+        true
+    }
+
+    // Swap the wNEAR if Ref took all of it, otherwise unwrap what came back and refund the buyer
+    #[private]
+    pub fn resolve_ref_deposit(
+        &mut self,
+        #[callback_result] used_amount: Result<U128, PromiseError>,
+        account_id: AccountId,
+        pet_id: PetId,
+        item_id: ItemId,
+        amount_in: U128,
+        min_amount_out: U128,
+    ) -> bool {
+        let ref_exchange = self.ref_exchange.clone().expect("NEAR purchases are off");
+        let used_amount = used_amount.map_or(0, |used_amount| used_amount.0);
+        if used_amount != amount_in.0 {
+            let refund = amount_in.0 - used_amount;
+            log!("Ref took {} of the {} wNEAR of {}", used_amount, amount_in.0, account_id);
+            cross_wnear::ext(ref_exchange.wnear_id)
+                .with_static_gas(GAS_FOR_CROSS_CALL)
+                .with_attached_deposit(ATTACHED_TRANSFER_FT)
+                .near_withdraw(U128(refund));
+            Promise::new(account_id).transfer(refund);
+            return false;
+        }
+
+        let action = SwapAction {
+            pool_id: ref_exchange.pool_id,
+            token_in: ref_exchange.wnear_id,
+            amount_in: Some(amount_in),
+            token_out: self.ft_address.clone(),
+            min_amount_out,
+        };
+        cross_ref_exchange::ext(ref_exchange.ref_exchange_id)
+            .with_static_gas(GAS_FOR_REF_SWAP)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .swap(vec![action], None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_BUY_WITH_NEAR)
+                    .resolve_buy_item_immidiate_with_near(account_id, pet_id, item_id, amount_in),
+            );
+        true
+    }
+
+    // Give the item if the JOY received pays for it, otherwise refund the buyer
+    #[private]
+    pub fn resolve_buy_item_immidiate_with_near(
+        &mut self,
+        #[callback_result] joy_received: Result<U128, PromiseError>,
+        account_id: AccountId,
+        pet_id: PetId,
+        item_id: ItemId,
+        amount_in: U128,
+    ) -> bool {
+        let ref_exchange = self.ref_exchange.clone().expect("NEAR purchases are off");
+
+        // The swap failed, the wNEAR is taken back from Ref, then unwrapped and sent back in the callbacks
+        let joy_received = match joy_received {
+            Ok(joy_received) => joy_received.0,
+            Err(_) => {
+                cross_ref_exchange::ext(ref_exchange.ref_exchange_id)
+                    .with_static_gas(GAS_FOR_REF_WITHDRAW)
+                    .with_attached_deposit(ATTACHED_TRANSFER_FT)
+                    .withdraw(ref_exchange.wnear_id, amount_in, None)
+                    .then(
+                        Self::ext(env::current_account_id())
+                            .with_static_gas(GAS_FOR_RESOLVE_REF_WITHDRAW)
+                            .resolve_refund_wnear_withdraw(account_id, amount_in),
+                    );
+                return false;
+            }
+        };
+
+        // The item could have been bought out or its price raised during the swap
+        let can_buy = self.internal_item_immidiate_purchase_error(pet_id, item_id, &account_id).is_none()
+            && self
+                .item_immidiate_metadata_by_id
+                .get(&item_id)
+                .is_some_and(|item| joy_received >= item.price + item.price_delta);
+        let price = if can_buy {
+            self.internal_feed_item_immidiate(pet_id, item_id)
+        } else {
+            0
+        };
+
+        // The JOY is burned and the rest sent to the buyer once it is out of Ref
+        cross_ref_exchange::ext(ref_exchange.ref_exchange_id)
+            .with_static_gas(GAS_FOR_REF_WITHDRAW)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .withdraw(self.ft_address.clone(), U128(joy_received), None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_REF_WITHDRAW)
+                    .resolve_joy_withdraw(account_id, U128(price), U128(joy_received - price)),
+            );
+
+        can_buy
+    }
+
+    // Unwrap the refunded wNEAR if Ref sent it back
+    #[private]
+    pub fn resolve_refund_wnear_withdraw(&mut self, account_id: AccountId, amount_in: U128) -> bool {
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            log!("The {} wNEAR of {} couldn't be withdrawn from Ref", amount_in.0, account_id);
+            return false;
+        }
+
+        let ref_exchange = self.ref_exchange.clone().expect("NEAR purchases are off");
+        cross_wnear::ext(ref_exchange.wnear_id)
+            .with_static_gas(GAS_FOR_CROSS_CALL)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .near_withdraw(amount_in)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_NEAR_WITHDRAW)
+                    .resolve_refund_near_withdraw(account_id, amount_in),
+            );
+        true
+    }
+
+    // Send the NEAR back to the buyer if the wNEAR was unwrapped
+    #[private]
+    pub fn resolve_refund_near_withdraw(&mut self, account_id: AccountId, amount_in: U128) -> bool {
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            log!("The {} wNEAR of {} couldn't be unwrapped", amount_in.0, account_id);
+            return false;
+        }
+
+        Promise::new(account_id).transfer(amount_in.0);
+        true
+    }
+
+    // Burn the price and send the JOY left to the buyer if Ref sent the JOY
+    #[private]
+    pub fn resolve_joy_withdraw(&mut self, account_id: AccountId, price: U128, refund: U128) -> bool {
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            log!("The {} JOY of {} couldn't be withdrawn from Ref", price.0 + refund.0, account_id);
+            return false;
+        }
+
+        if price.0 > 0 {
+            cross_ft::ext(self.ft_address.to_owned())
+                .with_static_gas(GAS_FOR_CROSS_CALL)
+                .ft_burn(env::current_account_id(), price);
+        }
+        if refund.0 > 0 {
+            cross_ft::ext(self.ft_address.to_owned())
+                .with_static_gas(GAS_FOR_CROSS_CALL)
+                .with_attached_deposit(ATTACHED_TRANSFER_FT)
+                .ft_transfer(account_id, refund, None);
+        }
+        true
+    }
+}
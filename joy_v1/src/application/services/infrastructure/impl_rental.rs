@@ -0,0 +1,184 @@
+use near_sdk::{env, json_types::U128, near_bindgen, AccountId, Gas, PromiseOrValue, PromiseResult};
+
+use crate::models::{
+    contract::{JoychiV1, JoychiV1Ext},
+    ft_request::external::cross_ft,
+    nft_request::external::cross_pet_nft,
+    pet::PetFeature,
+    rental::{Rental, RentalOffer},
+    PetId,
+};
+
+use super::impl_pet::GAS_FOR_CROSS_CALL;
+use super::impl_staking_and_mining::{ATTACHED_LOCK_NFT, ATTACHED_TRANSFER_FT, GAS_FOR_NFT_LOCK};
+
+pub const LOCK_REASON_RENTAL: &str = "rental";
+pub const GAS_FOR_RESOLVE_RENT_PET: Gas = Gas(20_000_000_000_000);
+
+#[near_bindgen]
+impl Rental for JoychiV1 {
+    fn offer_pet_for_rent(&mut self, pet_id: PetId, duration: u128, fee: U128) -> RentalOffer {
+        let pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
+
+        assert!(
+            pet.owner_id == env::signer_account_id(),
+            "You're not owner this pet"
+        );
+        assert!(!pet.is_lock, "Your pet is locked, you cannot rent it");
+        assert!(duration > 0, "Rental duration must be greater than 0");
+        assert!(
+            self.rental_by_pet_id.get(&pet_id).is_none_or(|rental| rental.renter_id.is_none()),
+            "Pet is rented"
+        );
+
+        let rental = RentalOffer {
+            pet_id,
+            owner_id: pet.owner_id,
+            duration,
+            fee,
+            renter_id: None,
+            rented_at: 0,
+            expires_at: 0,
+        };
+
+        self.rental_by_pet_id.insert(&pet_id, &rental);
+
+        rental
+    }
+
+    fn cancel_rental_offer(&mut self, pet_id: PetId) {
+        let rental = self.rental_by_pet_id.get(&pet_id).expect("No rental offer");
+
+        assert!(
+            rental.owner_id == env::signer_account_id(),
+            "You're not owner this pet"
+        );
+        assert!(rental.renter_id.is_none(), "Pet is rented");
+
+        self.rental_by_pet_id.remove(&pet_id);
+    }
+
+    fn end_rental(&mut self, pet_id: PetId) {
+        let mut rental = self.rental_by_pet_id.get(&pet_id).expect("No rental offer");
+        let mut pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
+
+        let renter_id = rental.renter_id.clone().expect("Pet is not rented");
+        assert!(
+            env::block_timestamp() as u128 >= rental.expires_at,
+            "Rental has not expired yet"
+        );
+
+        // The offer stays up so the pet can be rented again
+        rental.renter_id = None;
+        rental.rented_at = 0;
+        rental.expires_at = 0;
+        pet.is_lock = false;
+        if let Some(index) = pet.extra_permission.iter().position(|user_id| user_id == &renter_id) {
+            pet.extra_permission.remove(index);
+        }
+
+        self.rental_by_pet_id.insert(&pet_id, &rental);
+        self.pet_metadata_by_id.insert(&pet_id, &pet);
+
+        cross_pet_nft::ext(self.nft_address.to_owned())
+            .with_static_gas(GAS_FOR_NFT_LOCK)
+            .with_attached_deposit(ATTACHED_LOCK_NFT)
+            .nft_unlock(pet_id.to_string());
+    }
+
+    fn check_role_play_pet(&self, pet_id: PetId, user_id: AccountId) -> bool {
+        let pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
+        let is_rented = self.is_pet_rented(pet_id);
+
+        // The renter is delegated the pet until the rental expires, end_rental only cleans the delegation up
+        let is_renter = self
+            .rental_by_pet_id
+            .get(&pet_id)
+            .is_some_and(|rental| rental.renter_id.as_ref() == Some(&user_id));
+        if is_renter {
+            return is_rented;
+        }
+
+        if pet.extra_permission.contains(&user_id) {
+            return true;
+        }
+
+        pet.owner_id == user_id && !is_rented
+    }
+}
+
+impl JoychiV1 {
+    // A pet is rented until its rental expires, even if end_rental wasn't called yet
+    pub fn is_pet_rented(&self, pet_id: PetId) -> bool {
+        self.rental_by_pet_id.get(&pet_id).is_some_and(|rental| {
+            rental.renter_id.is_some() && (env::block_timestamp() as u128) < rental.expires_at
+        })
+    }
+
+    // Rent a pet with the JOY sent through ft_transfer_call, the unused JOY is returned once the pet NFT is locked
+    pub fn internal_rent_pet(&mut self, renter_id: AccountId, pet_id: PetId, amount: U128) -> PromiseOrValue<U128> {
+        let mut rental = self.rental_by_pet_id.get(&pet_id).expect("No rental offer");
+        let mut pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
+
+        assert!(rental.owner_id == pet.owner_id, "Rental offer is outdated");
+        assert!(renter_id != pet.owner_id, "You can't rent your own pet");
+        assert!(rental.renter_id.is_none(), "Pet is rented");
+        assert!(!pet.is_lock, "Your pet is locked, you cannot rent it");
+        assert!(self.is_pet_alive(pet_id), "Pet's not alive");
+        assert!(amount.0 >= rental.fee.0, "Not enough JOY to rent this pet");
+
+        // The pet is reserved for the renter until the lock is resolved
+        let now = env::block_timestamp() as u128;
+        rental.renter_id = Some(renter_id.clone());
+        rental.rented_at = now;
+        rental.expires_at = now + rental.duration;
+        pet.is_lock = true;
+
+        self.rental_by_pet_id.insert(&pet_id, &rental);
+        self.pet_metadata_by_id.insert(&pet_id, &pet);
+
+        // The pet NFT can't be transferred while it's rented
+        cross_pet_nft::ext(self.nft_address.to_owned())
+            .with_static_gas(GAS_FOR_NFT_LOCK)
+            .with_attached_deposit(ATTACHED_LOCK_NFT)
+            .nft_lock(pet_id.to_string(), rental.owner_id, LOCK_REASON_RENTAL.to_string())
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_RENT_PET)
+                    .resolve_rent_pet(renter_id, pet_id, amount),
+            )
+            .into()
+    }
+}
+
+#[near_bindgen]
+impl JoychiV1 {
+    // Pay the owner and delegate the pet to the renter if the pet NFT was locked, refund the renter otherwise
+    #[private]
+    pub fn resolve_rent_pet(&mut self, renter_id: AccountId, pet_id: PetId, amount: U128) -> U128 {
+        let mut rental = self.rental_by_pet_id.get(&pet_id).unwrap();
+        let mut pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
+
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            rental.renter_id = None;
+            rental.rented_at = 0;
+            rental.expires_at = 0;
+            pet.is_lock = false;
+
+            self.rental_by_pet_id.insert(&pet_id, &rental);
+            self.pet_metadata_by_id.insert(&pet_id, &pet);
+
+            return amount;
+        }
+
+        pet.extra_permission.push(renter_id);
+        self.pet_metadata_by_id.insert(&pet_id, &pet);
+
+        cross_ft::ext(self.ft_address.to_owned())
+            .with_static_gas(GAS_FOR_CROSS_CALL)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .ft_transfer(rental.owner_id, rental.fee, None);
+
+        U128(amount.0 - rental.fee.0)
+    }
+}
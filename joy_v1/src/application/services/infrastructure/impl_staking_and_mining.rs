@@ -1,16 +1,36 @@
-use near_sdk::{collections::LookupMap, env, json_types::U128, near_bindgen, AccountId};
+use near_contract_standards::non_fungible_token::TokenId;
+use near_sdk::{collections::LookupMap, env, json_types::U128, log, near_bindgen, AccountId, Gas, PromiseResult};
 
+use crate::application::repository::random_in_range;
 use crate::models::{
-    contract::{JoychiV1, JoychiV1Ext, JoychiV1StorageKey}, ft_request::external::cross_ft, item_factory::{ItemFeature, ItemType}, pet::PetFeature, staking_and_mining::{MiningData, NFTInfo, PoolInfo, PoolMetadata, StakingAndMining}, PetId, PoolId
+    contract::{JoychiV1, JoychiV1Ext, JoychiV1StorageKey}, ft_request::external::cross_ft, item_factory::ItemType, nft_request::external::{cross_item_nft, cross_pet_nft}, pet::{PetFeature, PetMetadata}, staking_and_mining::{LockTerm, MiningData, MiningDifficulty, NFTInfo, PenaltyReceiver, PoolEligibility, PoolInfo, PoolMetadata, StakingAndMining}, ItemId, PetId, PoolId
 };
 pub const ATTACHED_TRANSFER_FT: u128 = 1;
+pub const GAS_FOR_NFT_TOKEN: Gas = Gas(5_000_000_000_000);
+pub const GAS_FOR_NFT_LOCK: Gas = Gas(10_000_000_000_000);
+pub const ATTACHED_LOCK_NFT: u128 = 1;
+pub const GAS_FOR_NFT_TRANSFER: Gas = Gas(15_000_000_000_000);
+pub const ATTACHED_TRANSFER_NFT: u128 = 1;
+pub const BASIS_POINTS: u128 = 10_000;
+// How much the difficulty can change from one epoch to the next
+pub const MAX_DIFFICULTY_ADJUSTMENT: u128 = 4;
+pub const GAS_FOR_RESOLVE_REDEMN: Gas = Gas(30_000_000_000_000);
+pub const GAS_FOR_RESOLVE_REDEMN_ITEM: Gas = Gas(5_000_000_000_000);
 
 use super::impl_pet::GAS_FOR_CROSS_CALL;
 
 #[near_bindgen]
 impl StakingAndMining for JoychiV1 {
 
-    fn create_new_staking_pool(&mut self, name: String, reward_nft_ids: Vec<u128>, staking_start_time: u128, staking_end_time: u128, max_slot_in_pool: u128, token_reward_per_slot: u128, max_slot_per_wallet: u128) -> PoolMetadata {
+    fn create_new_staking_pool(&mut self, name: String, reward_nft_ids: Vec<u128>, staking_start_time: u128, staking_end_time: u128, max_slot_in_pool: u128, token_reward_per_slot: u128, max_slot_per_wallet: u128, eligibility: Option<PoolEligibility>) -> PoolMetadata {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+        assert!(staking_end_time > staking_start_time, "Staking pool must end after it starts");
+        assert!(max_slot_per_wallet > 0, "Max slot per wallet must be greater than 0");
+        token_reward_per_slot.checked_mul(max_slot_in_pool).expect("Total reward is too large");
+
         let new_pool = PoolInfo {
             name: name,
             reward_nft_ids: reward_nft_ids,
@@ -19,16 +39,25 @@ impl StakingAndMining for JoychiV1 {
             max_slot_in_pool: max_slot_in_pool,
             token_reward_per_slot: token_reward_per_slot,
             max_slot_per_wallet: max_slot_per_wallet,
-            total_staked_slot: 0
+            total_staked_slot: 0,
+            eligibility: eligibility.unwrap_or_default(),
+            lock_terms: Vec::new(),
+            early_exit_penalty_bps: 0,
+            penalty_receiver: PenaltyReceiver::Treasury,
         };
 
         let num_pool = self.all_pool_id.len();
 
         let new_pool_metadata = PoolMetadata {
             pool_id: num_pool + 1,
-            price_per_slot: 37,
+            price_per_slot: self.price_per_slot,
             pool_info: new_pool,
             staked_pets: Vec::new(),
+            total_reward_distributed: 0,
+            total_reward_funded: 0,
+            total_reward_withdrawn: 0,
+            is_active: false,
+            penalty_share_per_slot: 0,
         };
 
         self.pool_metadata_by_id.insert(&(&num_pool + 1), &new_pool_metadata);
@@ -38,205 +67,259 @@ impl StakingAndMining for JoychiV1 {
 
     }
 
-    fn stake(&mut self, pet_id: PetId, pool_id: PoolId) -> PoolMetadata {
+    #[payable]
+    fn un_stake(&mut self, pet_id: PetId, pool_id: PoolId) {
         let mut pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
         let mut pool = self.pool_metadata_by_id.get(&pool_id).unwrap();
         let account_id = env::signer_account_id();
+        let now = env::block_timestamp() as u128;
 
         assert!(pool.pool_id == pool_id, "Invalid pool id");
-        assert!(pool.pool_info.staking_start_time < env::block_timestamp() as u128, "Staking pool has started");
         assert!(
            account_id == pet.owner_id,
             "You're not owner this pet"
         );
 
-        assert!(self.is_pet_alive(pet_id), "Your pet is dead, you cannot stake it");
-        assert!(pet.is_lock == false, "Your pet is locked, you cannot stake it");
-        assert!(pool.pool_info.total_staked_slot < pool.pool_info.max_slot_in_pool , "Staking pool is full");
+        let pos = pool.staked_pets.iter()
+            .position(|staked| staked.owner == account_id && staked.nft_id == pet_id as u128)
+            .expect("Pet not staked");
+        assert!(pet.is_lock == true, "Your pet is not locked, you cannot unstake it");
 
-        pet.is_lock = true;
+        // Stakes without a lock term stay until the pool ends, locked stakes can leave early with a penalty
+        let nft_info = pool.staked_pets.remove(pos);
+        if nft_info.lock_until == 0 {
+            assert!(pool.pool_info.staking_end_time < now, "Staking pool has not ended yet");
+        }
+        let early_exit = now < nft_info.lock_until;
+
+        // Pay what's left of the reward of the slot
+        let pending = self.internal_staking_reward(&pool, &nft_info) - nft_info.reward_claimed;
+        let penalty = if early_exit { pending * pool.pool_info.early_exit_penalty_bps / BASIS_POINTS } else { 0 };
+        let reward = pending - penalty;
+        pool.total_reward_distributed += reward;
+        pool.pool_info.total_staked_slot -= 1;
+        pet.is_lock = false;
 
-        let mut inner_map = self.user_staked_pet_count.get(&account_id).unwrap_or_else(|| {
-            LookupMap::new(JoychiV1StorageKey::UserStakedPetCountInner { account_id: account_id.clone() })
-        });
+        let mut penalty_to_treasury = 0;
+        if penalty > 0 {
+            if pool.pool_info.penalty_receiver == PenaltyReceiver::Stakers && pool.pool_info.total_staked_slot > 0 {
+                pool.penalty_share_per_slot += penalty / pool.pool_info.total_staked_slot;
+            } else {
+                penalty_to_treasury = penalty;
+                pool.total_reward_distributed += penalty;
+            }
+        }
 
+        let mut inner_map = self.user_staked_pet_count.get(&account_id).unwrap();
         let current_count = inner_map.get(&pool_id).unwrap_or(0);
-        inner_map.insert(&pool_id, &(current_count + 1));
+        inner_map.insert(&pool_id, &current_count.saturating_sub(1));
         self.user_staked_pet_count.insert(&account_id, &inner_map);
 
-        pool.pool_info.total_staked_slot += 1;
-
-        let nft_info = NFTInfo {
-            nft_id: pet_id as u128,
-            owner: account_id
-        };
-
-        pool.staked_pets.push(nft_info);
-        
         self.pool_metadata_by_id.insert(&pool_id, &pool);
         self.pet_metadata_by_id.insert(&pet_id, &pet);
-        
-        pool
 
-    }
-    
-    #[payable]
-    fn un_stake(&mut self, pet_id: PetId, pool_id: PoolId) {
-        let mut pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
-        let pool = self.pool_metadata_by_id.get(&pool_id).unwrap();
-        let account_id = env::signer_account_id();
-        let mut is_staked_pet_owner = false;
-        for owner in pool.staked_pets {
-            if owner.owner == account_id && owner.nft_id == pet_id as u128 {
-                is_staked_pet_owner = true;
+        if !early_exit {
+            for reward in pool.pool_info.reward_nft_ids {
+                self.internal_mint_item(account_id.clone(), reward as u64);
             }
         }
 
-        assert!(is_staked_pet_owner, "Pet not staked");
-
-        assert!(pool.pool_id == pool_id, "Invalid pool id");
-        assert!(pool.pool_info.staking_end_time < env::block_timestamp() as u128, "Staking pool has not ended yet");
-        assert!(
-           account_id == pet.owner_id,
-            "You're not owner this pet"
-        );
-
-        assert!(self.is_pet_alive(pet_id), "Your pet is dead, you cannot stake it");
-        assert!(pet.is_lock == true, "Your pet is not locked, you cannot unstake it");
-
-        pet.is_lock = false;
-
-        for reward in pool.pool_info.reward_nft_ids {
-            self.mint_item_for_user(account_id.clone(), reward as u64);
+        if reward > 0 {
+            cross_ft::ext(self.ft_address.to_owned())
+                .with_static_gas(GAS_FOR_CROSS_CALL)
+                .with_attached_deposit(ATTACHED_TRANSFER_FT)
+                .ft_transfer(account_id.clone(), U128::from(reward), None);
         }
 
-        cross_ft::ext(self.ft_address.to_owned())
-            .with_static_gas(GAS_FOR_CROSS_CALL)
-            .with_attached_deposit(ATTACHED_TRANSFER_FT)
-            .ft_transfer(pet.owner_id.clone(), U128::from(pool.price_per_slot), None);
+        if penalty_to_treasury > 0 {
+            cross_ft::ext(self.ft_address.to_owned())
+                .with_static_gas(GAS_FOR_CROSS_CALL)
+                .with_attached_deposit(ATTACHED_TRANSFER_FT)
+                .ft_transfer(self.treasury_id.clone(), U128::from(penalty_to_treasury), Some("early unstake penalty".to_string()));
+        }
 
-        self.pet_metadata_by_id.insert(&pet_id, &pet);
+        // Give the escrowed pet NFT back
+        cross_pet_nft::ext(self.nft_address.to_owned())
+            .with_static_gas(GAS_FOR_NFT_TRANSFER)
+            .with_attached_deposit(ATTACHED_TRANSFER_NFT)
+            .nft_transfer(account_id, pet_id.to_string(), None, Some("unstake".to_string()));
 
     }
 
-    fn add_mining_tool(&mut self, tool_id: u64) {
+    fn claim_staking_reward(&mut self, pet_id: PetId, pool_id: PoolId) -> U128 {
+        let mut pool = self.pool_metadata_by_id.get(&pool_id).expect("Staking pool doesn't exist");
         let account_id = env::signer_account_id();
-        let mut item = self.item_metadata_by_id.get(&tool_id).unwrap();
-
-        let item_type = item.prototype_item_type.clone();
-        assert!(item_type == ItemType::MineTool, "This item is not a mining tool");        
 
-        let mining_data_by_account = self.mining_data_by_account_id.get(&account_id);
+        let pos = pool.staked_pets.iter()
+            .position(|staked| staked.owner == account_id && staked.nft_id == pet_id as u128)
+            .expect("Pet not staked");
 
+        assert!(env::block_timestamp() as u128 >= pool.staked_pets[pos].lock_until, "Reward is locked until {}", pool.staked_pets[pos].lock_until);
 
-        if let Some(mut mining_data) = mining_data_by_account {
-            assert!(mining_data.account_id.clone().unwrap() == account_id, "You're not owned");
-            assert!(self.item_metadata_by_id.get(&tool_id).unwrap().is_lock == true, "This tool must be locked");
-            assert!(mining_data.mining_tool_used.len() < 3, "You have reached the maximum mining tool");
-            assert!(mining_data.mining_tool_used.contains(&(tool_id as u128))  == false, "Duplicate mining tool");
+        let accrued = self.internal_staking_reward(&pool, &pool.staked_pets[pos]);
+        let reward = accrued - pool.staked_pets[pos].reward_claimed;
+        assert!(reward > 0, "No reward to claim");
 
+        pool.staked_pets[pos].reward_claimed = accrued;
+        pool.total_reward_distributed += reward;
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
 
-            let mining_power = self.item_metadata_by_id.get(&tool_id).unwrap().prototype_itemmining_power;
-
-            mining_data.mining_tool_used.push(tool_id as u128);
-            
+        cross_ft::ext(self.ft_address.to_owned())
+            .with_static_gas(GAS_FOR_CROSS_CALL)
+            .with_attached_deposit(ATTACHED_TRANSFER_FT)
+            .ft_transfer(account_id, U128::from(reward), None);
 
-            if mining_data.last_mining_time == 0 || mining_data.mining_tool_used.len() == 1 {
-                let last_time_mining = env::block_timestamp() as u128;
-                mining_data.last_mining_time += last_time_mining;
-            }
-            mining_data.total_mining_power += mining_power;
-            
-            self.mining_data_by_account_id.insert(&account_id, &mining_data);
+        U128(reward)
+    }
 
+    fn set_pool_lock_terms(&mut self, pool_id: PoolId, lock_terms: Vec<LockTerm>, early_exit_penalty_bps: u128, penalty_receiver: PenaltyReceiver) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+        let mut pool = self.pool_metadata_by_id.get(&pool_id).expect("Staking pool doesn't exist");
+
+        // The multipliers change the rewards the pool has to hold, so they're set before any pet is staked
+        assert!(pool.staked_pets.is_empty(), "Staking pool already has staked pets");
+        assert!(early_exit_penalty_bps <= BASIS_POINTS, "Penalty can't be more than 100%");
+        for lock_term in lock_terms.iter() {
+            assert!(lock_term.duration > 0, "Lock duration must be greater than 0");
+            assert!(lock_term.multiplier_bps >= BASIS_POINTS, "Multiplier can't be less than x1");
         }
-        else {
-            let mut mining_used = Vec::new();
-            mining_used.push(tool_id as u128);
-            let mining_data = MiningData {
-                account_id: Some(account_id.clone()),
-                mining_points: 0,
-                last_mining_time: 0,
-                total_mining_power: 0,
-                total_mining_charge_time: 0,
-                mining_tool_used : mining_used,
-            };
 
-            self.mining_data_by_account_id.insert(&account_id, &mining_data);
+        pool.pool_info.lock_terms = lock_terms;
+        pool.pool_info.early_exit_penalty_bps = early_exit_penalty_bps;
+        pool.pool_info.penalty_receiver = penalty_receiver;
 
-        }
-        item.is_lock = true;
-        item.owner = account_id;
-        self.item_metadata_by_id.insert(&tool_id, &item);
+        // A funded pool is deactivated until the JOY the new multipliers need is sent
+        let total_reward = self.internal_pool_total_reward(&pool.pool_info);
+        assert!(pool.total_reward_funded <= total_reward, "Staking pool is funded above the new total reward");
+        pool.is_active = pool.total_reward_funded == total_reward;
 
-        
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
     }
 
-    fn remove_mining_tool(&mut self, tool_id: u64) {
+    fn set_treasury(&mut self, treasury_id: AccountId) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+        self.treasury_id = treasury_id;
+    }
+
+    fn remove_mining_tool(&mut self, token_id: TokenId) {
         let account_id = env::signer_account_id();
-        let mut item = self.item_metadata_by_id.get(&tool_id).unwrap();
-        assert!(item.owner == account_id, "You are not the owner of this tool");
+        let mut item_instance = self.item_instance_by_token_id.get(&token_id).expect("Item instance doesn't exist");
+        let item = self.item_metadata_by_id.get(&item_instance.item_id).unwrap();
 
         let mut mining_data = self.mining_data_by_account_id.get(&account_id).expect("Mining tool should not be empty");
+        let pos = mining_data.mining_tool_used.iter().position(|x| x == &token_id).expect("You are not using this tool");
 
-        if mining_data.total_mining_power > item.prototype_itemmining_power {
-            mining_data.total_mining_power -= item.prototype_itemmining_power;
-        
-            if let Some(pos) = mining_data.mining_tool_used.iter().position(|&x| x == tool_id as u128) {
-                mining_data.mining_tool_used.remove(pos);
-            }
-            item.is_lock = false;
-            self.mining_data_by_account_id.insert(&account_id, &mining_data);
-            self.item_metadata_by_id.insert(&tool_id, &item);
-        }
+        mining_data.mining_tool_used.remove(pos);
+        mining_data.total_mining_power = mining_data.total_mining_power.saturating_sub(item.prototype_itemmining_power);
+        self.mining_difficulty.network_mining_power = self.mining_difficulty.network_mining_power.saturating_sub(item.prototype_itemmining_power);
+        mining_data.total_mining_charge_time = self.internal_mining_charge_time(&mining_data.mining_tool_used);
+        item_instance.is_lock = false;
 
+        self.mining_data_by_account_id.insert(&account_id, &mining_data);
+        self.item_instance_by_token_id.insert(&token_id, &item_instance);
 
+        // Give the escrowed tool NFT back
+        cross_item_nft::ext(self.nft_item_address.to_owned())
+            .with_static_gas(GAS_FOR_NFT_TRANSFER)
+            .with_attached_deposit(ATTACHED_TRANSFER_NFT)
+            .nft_transfer(account_id, token_id, None, Some("remove mining tool".to_string()));
     }
 
     fn mining(&mut self) {
         let account_id = env::signer_account_id();
         let mut mining_data = self.mining_data_by_account_id.get(&account_id).unwrap();
+        self.internal_sync_mining_season(&mut mining_data);
         assert!(mining_data.total_mining_power > 0, "You do not have any mining tool");
 
-        let total_mining_charge_of_time = (1000 * mining_data.total_mining_charge_time) / 1000 as u128; // TODO
-
-        assert!(mining_data.last_mining_time + total_mining_charge_of_time > env::block_timestamp() as u128, "You need to wait for the mining tool to be charged");
+        assert!(env::block_timestamp() as u128 >= mining_data.last_mining_time + mining_data.total_mining_charge_time, "You need to wait for the mining tool to be charged");
         mining_data.last_mining_time = env::block_timestamp() as u128;
+        assert!(self.internal_mining_working_power(&mining_data) > 0, "Your mining tools need to be repaired");
 
-        let total_points_mined = (10000 * mining_data.total_mining_power) / 1000 as u128;
+        // Once seasons are set up, points can only be mined during a season
+        let active_season = self.internal_active_season();
+        assert!(self.mining_season_count == 0 || active_season.is_some(), "No active mining season");
+
+        self.internal_roll_mining_epoch();
+        let total_points_mined = self.internal_mining_points(&mining_data);
 
         mining_data.mining_points += total_points_mined;
 
+        // The difficulty is retargeted on the points at a x1 emission, so it doesn't cancel the halving out
+        let emission_bps = active_season.as_ref().map_or(BASIS_POINTS, |season| season.emission_bps);
+        self.mining_difficulty.points_issued_in_epoch += (total_points_mined * BASIS_POINTS).checked_div(emission_bps).unwrap_or(0);
+
+        if let Some(mut season) = active_season {
+            self.internal_record_season_points(&mut season, &mut mining_data, account_id.clone(), total_points_mined);
+        }
+
+        // Every mining wears the tools that are still working
+        for token_id in mining_data.mining_tool_used.iter() {
+            let mut item_instance = self.item_instance_by_token_id.get(token_id).unwrap();
+            if item_instance.durability > 0 {
+                item_instance.durability -= 1;
+                self.item_instance_by_token_id.insert(token_id, &item_instance);
+                self.internal_sync_item_token_metadata(&item_instance);
+            }
+        }
+
         self.mining_data_by_account_id.insert(&account_id, &mining_data);
     }
 
     fn redemn_mining_points(&mut self) {
         let account_id = env::signer_account_id();
         let mut mining_data = self.mining_data_by_account_id.get(&account_id).unwrap();
- 
-        let mut mining_points = mining_data.mining_points;
+        self.internal_sync_mining_season(&mut mining_data);
+        let points = self.points_used_per_redemn;
 
-        assert!(mining_points > self.points_used_per_redemn, "You do not have enough mining point");
-        mining_points -= &self.points_used_per_redemn;
+        // The points are spent now and given back if the JOY can't be paid
+        assert!(mining_data.mining_points >= points, "You do not have enough mining point");
+        mining_data.mining_points -= points;
+        self.mining_data_by_account_id.insert(&account_id, &mining_data);
 
-        // Mint New Item TODO
+        // A random item of the redemption table comes with the JOY
+        let item_id = if self.redemption_item_ids.is_empty() {
+            None
+        } else {
+            let index = random_in_range(0, self.redemption_item_ids.len() as i64 - 1) as usize;
+            Some(self.redemption_item_ids[index])
+        };
 
         cross_ft::ext(self.ft_address.to_owned())
             .with_static_gas(GAS_FOR_CROSS_CALL)
             .with_attached_deposit(ATTACHED_TRANSFER_FT)
-            .ft_transfer(account_id.clone(), U128::from(self.token_earned_per_redemn), None);
-
+            .ft_transfer(account_id.clone(), U128::from(self.token_earned_per_redemn), None)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_REDEMN)
+                    .resolve_redemn_mining_points(account_id, U128(points), item_id),
+            );
     }
 
     fn owner_withdraw_redundant_token(&mut self, pool_id: PoolId) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
         let account_id = env::signer_account_id();
         let mut pool = self.pool_metadata_by_id.get(&pool_id).unwrap();
         assert!(pool.pool_info.staking_end_time < (env::block_timestamp() as u128),  "Staking pool has not ended yet");
 
-        let total_tokens_for_reward = pool.pool_info.token_reward_per_slot * pool.pool_info.max_slot_in_pool;
-        let token_distributed = pool.pool_info.token_reward_per_slot * pool.pool_info.total_staked_slot;
+        // Slots still staked keep what they haven't claimed yet
+        let token_owed: u128 = pool.staked_pets.iter()
+            .map(|staked| self.internal_staking_reward(&pool, staked) - staked.reward_claimed)
+            .sum();
+
+        let redundant_token = self.internal_pool_remaining(&pool) - token_owed;
+        assert!(redundant_token > 0, "No redundant token to withdraw");
 
-        let redundant_token = total_tokens_for_reward - token_distributed;
+        pool.total_reward_withdrawn += redundant_token;
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
 
         cross_ft::ext(self.ft_address.to_owned())
             .with_static_gas(GAS_FOR_CROSS_CALL)
@@ -245,42 +328,368 @@ impl StakingAndMining for JoychiV1 {
     }
 
     fn configure_mining_pool(&mut self, name: String, mining_power_multiplier: u128, charge_of_time_multiplier: u128) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
         self.mining_pool_name = name;
         self.mining_power_multiplier = mining_power_multiplier;
         self.charge_of_time_multiplier = charge_of_time_multiplier;
     }
 
+    fn configure_mining_difficulty(&mut self, epoch_duration: u128, target_points_per_epoch: u128) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+
+        // The difficulty starts again from x1, a target of 0 turns the adjustment off
+        self.mining_difficulty = MiningDifficulty {
+            difficulty_bps: BASIS_POINTS,
+            network_mining_power: self.mining_difficulty.network_mining_power,
+            epoch_duration,
+            target_points_per_epoch,
+            epoch_start: env::block_timestamp() as u128,
+            epoch_start_network_power: self.mining_difficulty.network_mining_power,
+            points_issued_in_epoch: 0,
+        };
+    }
+
     fn set_mining_points_used_per_redemn(&mut self, points: u128) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+        assert!(points > 0, "Points used per redemption must be greater than 0");
         self.points_used_per_redemn = points;
     }
 
     fn set_token_earned_per_redemn(&mut self, token: u128) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
         self.token_earned_per_redemn = token;
     }
 
+    fn set_redemption_items(&mut self, item_ids: Vec<ItemId>) {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+        for item_id in item_ids.iter() {
+            assert!(self.item_metadata_by_id.get(item_id).is_some(), "Item doesn't exist");
+        }
+        self.redemption_item_ids = item_ids;
+    }
+
     fn set_price_per_slot(&mut self, price_per_slot: u128) {
         self.price_per_slot = price_per_slot;
     }
 
-    fn caculate_charge_of_time(&mut self, number_of_tools: u128) {
-        if number_of_tools == 1 {
-            // let charge_of_time = self.mining
+    // Every tool after the first adds charge_of_time_multiplier of the average charge time (in basis points)
+    fn caculate_charge_of_time(&self, number_of_tools: u128) -> u128 {
+        if number_of_tools == 0 {
+            return 0;
         }
+
+        BASIS_POINTS + (number_of_tools - 1) * self.charge_of_time_multiplier
     }
+}
+
+impl JoychiV1 {
+    // The reward of a slot accrues linearly from when the pet is staked (at the earliest the start of the pool)
+    // to the end of the pool, scaled by the multiplier of its lock term, plus its share of the early unstake penalties
+    pub fn internal_staking_reward(&self, pool: &PoolMetadata, nft_info: &NFTInfo) -> u128 {
+        let pool_info = &pool.pool_info;
+        let penalty_share = pool.penalty_share_per_slot - nft_info.penalty_share_debt;
+
+        let now = env::block_timestamp() as u128;
+        let from = nft_info.staked_at.max(pool_info.staking_start_time);
+        let to = now.min(pool_info.staking_end_time);
+        if to <= from {
+            return penalty_share;
+        }
 
-    fn remove_item_from_list_tool(&mut self, value: u128) {
-        let account_id = env::signer_account_id();
-        let mut mining_data = self.mining_data_by_account_id.get(&account_id).unwrap();
+        // Split the product so large rewards don't overflow
+        let duration = pool_info.staking_end_time - pool_info.staking_start_time;
+        let elapsed = to - from;
+        let reward = pool_info.token_reward_per_slot / duration * elapsed
+            + pool_info.token_reward_per_slot % duration * elapsed / duration;
+
+        reward * nft_info.multiplier_bps / BASIS_POINTS + penalty_share
+    }
+
+    // The JOY a pool needs to pay every slot at the highest multiplier
+    pub fn internal_pool_total_reward(&self, pool_info: &PoolInfo) -> u128 {
+        let max_multiplier_bps = pool_info.lock_terms.iter()
+            .map(|lock_term| lock_term.multiplier_bps)
+            .fold(BASIS_POINTS, u128::max);
+
+        (pool_info.token_reward_per_slot * pool_info.max_slot_in_pool)
+            .checked_mul(max_multiplier_bps)
+            .expect("Total reward is too large")
+            / BASIS_POINTS
+    }
+
+    // Why a pet can't be staked in a pool, empty if it matches every rule of the pool
+    pub fn internal_pool_ineligibility_reasons(&self, pool_info: &PoolInfo, pet: &PetMetadata) -> Vec<String> {
+        let eligibility = &pool_info.eligibility;
+        let mut reasons = Vec::new();
+
+        if let Some(species_ids) = &eligibility.species_ids {
+            if !species_ids.contains(&pet.pet_species) {
+                reasons.push(format!("Species {} is not allowed", pet.pet_species));
+            }
+        }
+        if let Some(min_level) = eligibility.min_level {
+            if pet.level < min_level {
+                reasons.push(format!("Level {} is below {}", pet.level, min_level));
+            }
+        }
+        if let Some(min_evolution_phase) = eligibility.min_evolution_phase {
+            if pet.pet_evolution_phase < min_evolution_phase {
+                reasons.push(format!("Evolution phase {} is below {}", pet.pet_evolution_phase, min_evolution_phase));
+            }
+        }
+        if let Some(min_star) = eligibility.min_star {
+            if pet.star < min_star {
+                reasons.push(format!("Star {} is below {}", pet.star, min_star));
+            }
+        }
+        if let Some(whitelist) = &eligibility.whitelist {
+            if !whitelist.contains(&pet.owner_id) {
+                reasons.push(format!("{} is not whitelisted", pet.owner_id));
+            }
+        }
+
+        reasons
+    }
+
+    // The time the tools need to charge between two minings
+    pub fn internal_mining_charge_time(&self, mining_tool_used: &[TokenId]) -> u128 {
+        if mining_tool_used.is_empty() {
+            return 0;
+        }
+
+        let total_charge_time: u128 = mining_tool_used.iter()
+            .map(|token_id| {
+                let item_instance = self.item_instance_by_token_id.get(token_id).unwrap();
+                self.item_metadata_by_id.get(&item_instance.item_id).unwrap().prototype_itemmining_charge_time
+            })
+            .sum();
+        let number_of_tools = mining_tool_used.len() as u128;
+
+        total_charge_time / number_of_tools * self.caculate_charge_of_time(number_of_tools) / BASIS_POINTS
+    }
+
+    // The power of the tools that aren't worn out
+    pub fn internal_mining_working_power(&self, mining_data: &MiningData) -> u128 {
+        mining_data.mining_tool_used.iter()
+            .map(|token_id| self.item_instance_by_token_id.get(token_id).unwrap())
+            .filter(|item_instance| item_instance.durability > 0)
+            .map(|item_instance| self.item_metadata_by_id.get(&item_instance.item_id).unwrap().prototype_itemmining_power)
+            .sum()
+    }
+
+    // The points a mining gives, the working power scaled by the mining pool multiplier and the season emission, divided by the difficulty
+    pub fn internal_mining_points(&self, mining_data: &MiningData) -> u128 {
+        let emission_bps = self.internal_active_season().map_or(BASIS_POINTS, |season| season.emission_bps);
+
+        self.internal_mining_working_power(mining_data) * self.mining_power_multiplier * emission_bps
+            / self.mining_difficulty.difficulty_bps
+            / BASIS_POINTS
+    }
+
+    // Start a new epoch once the current one is over and rescale the difficulty to the points it issued
+    pub fn internal_roll_mining_epoch(&mut self) {
+        let now = env::block_timestamp() as u128;
+        let difficulty = &mut self.mining_difficulty;
+
+        if difficulty.epoch_duration == 0 || now < difficulty.epoch_start + difficulty.epoch_duration {
+            return;
+        }
+
+        // The points the epoch would have issued with the power the network has now
+        let expected_points = (difficulty.points_issued_in_epoch * difficulty.network_mining_power)
+            .checked_div(difficulty.epoch_start_network_power)
+            .unwrap_or(difficulty.points_issued_in_epoch);
+
+        // Without target or without any mining in the epoch the difficulty isn't adjusted,
+        // it can go below x1 when the network mines less than the target
+        let old_difficulty = difficulty.difficulty_bps;
+        let new_difficulty = (old_difficulty * expected_points).checked_div(difficulty.target_points_per_epoch);
+        if let Some(new_difficulty) = new_difficulty.filter(|_| difficulty.points_issued_in_epoch > 0) {
+            difficulty.difficulty_bps = new_difficulty
+                .clamp(old_difficulty / MAX_DIFFICULTY_ADJUSTMENT, old_difficulty * MAX_DIFFICULTY_ADJUSTMENT)
+                .max(1);
+        }
+
+        // Epochs without any mining are skipped
+        difficulty.epoch_start = now - (now - difficulty.epoch_start) % difficulty.epoch_duration;
+        difficulty.epoch_start_network_power = difficulty.network_mining_power;
+        difficulty.points_issued_in_epoch = 0;
+    }
+
+    // The JOY held by the pool that hasn't been paid out yet
+    pub fn internal_pool_remaining(&self, pool: &PoolMetadata) -> u128 {
+        pool.total_reward_funded - pool.total_reward_distributed - pool.total_reward_withdrawn
+    }
+
+    // Fund the rewards of a pool with the JOY sent through ft_transfer_call, returns the unused JOY
+    pub fn internal_fund_pool(&mut self, sender_id: AccountId, pool_id: PoolId, amount: U128) -> U128 {
+        assert!(self.owner_id == sender_id, "You're not permission");
+        let mut pool = self.pool_metadata_by_id.get(&pool_id).expect("Staking pool doesn't exist");
 
-        let mut user_list_mining_tool = mining_data.mining_tool_used.clone();
-        let last_tool = user_list_mining_tool.last().unwrap();
+        let total_reward = self.internal_pool_total_reward(&pool.pool_info);
+        let missing = total_reward - pool.total_reward_funded;
+        assert!(missing > 0, "Staking pool is already funded");
 
-        for i in 0..mining_data.mining_tool_used.len() {
-            if mining_data.mining_tool_used[i] == *last_tool {
-                    mining_data.mining_tool_used.pop();
-                    self.mining_data_by_account_id.insert(&account_id, &mining_data);
-                    break;
-                }
+        let funded = amount.0.min(missing);
+        pool.total_reward_funded += funded;
+
+        // Pets can be staked once every slot's reward is held by the pool
+        if pool.total_reward_funded == total_reward {
+            pool.is_active = true;
+        }
+
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+
+        U128(amount.0 - funded)
+    }
+
+    // Stake a pet escrowed through nft_transfer_call
+    pub fn internal_stake(&mut self, account_id: AccountId, pet_id: PetId, pool_id: PoolId, lock_term: Option<usize>) -> PoolMetadata {
+        let mut pet = self.pet_metadata_by_id.get(&pet_id).unwrap();
+        let mut pool = self.pool_metadata_by_id.get(&pool_id).unwrap();
+
+        assert!(pool.pool_id == pool_id, "Invalid pool id");
+        assert!(pool.is_active, "Staking pool is not funded");
+        assert!((env::block_timestamp() as u128) < pool.pool_info.staking_end_time, "Staking pool has ended");
+        assert!(
+           account_id == pet.owner_id,
+            "You're not owner this pet"
+        );
+
+        assert!(self.is_pet_alive(pet_id), "Your pet is dead, you cannot stake it");
+        assert!(pet.is_lock == false, "Your pet is locked, you cannot stake it");
+        assert!(pool.pool_info.total_staked_slot < pool.pool_info.max_slot_in_pool , "Staking pool is full");
+
+        let reasons = self.internal_pool_ineligibility_reasons(&pool.pool_info, &pet);
+        assert!(reasons.is_empty(), "Your pet is not eligible for this pool: {}", reasons.join(", "));
+
+        pet.is_lock = true;
+
+        let mut inner_map = self.user_staked_pet_count.get(&account_id).unwrap_or_else(|| {
+            LookupMap::new(JoychiV1StorageKey::UserStakedPetCountInner { account_id: account_id.clone() })
+        });
+
+        let current_count = inner_map.get(&pool_id).unwrap_or(0);
+        assert!((current_count as u128) < pool.pool_info.max_slot_per_wallet, "You have reached the maximum slot per wallet");
+        inner_map.insert(&pool_id, &(current_count + 1));
+        self.user_staked_pet_count.insert(&account_id, &inner_map);
+
+        pool.pool_info.total_staked_slot += 1;
+
+        // A lock term starts with the pool
+        let now = env::block_timestamp() as u128;
+        let (lock_until, multiplier_bps) = match lock_term {
+            Some(index) => {
+                let lock_term = pool.pool_info.lock_terms.get(index).expect("Invalid lock term");
+                (now.max(pool.pool_info.staking_start_time) + lock_term.duration, lock_term.multiplier_bps)
+            }
+            None => (0, BASIS_POINTS),
+        };
+
+        let nft_info = NFTInfo {
+            nft_id: pet_id as u128,
+            owner: account_id,
+            staked_at: now,
+            reward_claimed: 0,
+            lock_until,
+            multiplier_bps,
+            penalty_share_debt: pool.penalty_share_per_slot,
+        };
+
+        pool.staked_pets.push(nft_info);
+        
+        self.pool_metadata_by_id.insert(&pool_id, &pool);
+        self.pet_metadata_by_id.insert(&pet_id, &pet);
+
+        pool
+    }
+
+    // Use a tool escrowed through nft_transfer_call for mining
+    pub fn internal_add_mining_tool(&mut self, account_id: AccountId, token_id: TokenId) {
+        let mut item_instance = self.item_instance_by_token_id.get(&token_id).expect("Item instance doesn't exist");
+        let item = self.item_metadata_by_id.get(&item_instance.item_id).unwrap();
+
+        assert!(item.prototype_item_type == ItemType::MineTool, "This item is not a mining tool");
+        assert!(item_instance.is_lock == false, "This tool is already in use");
+
+        let mut mining_data = self.mining_data_by_account_id.get(&account_id).unwrap_or(MiningData {
+            account_id: Some(account_id.clone()),
+            mining_points: 0,
+            last_mining_time: 0,
+            total_mining_power: 0,
+            total_mining_charge_time: 0,
+            mining_tool_used: Vec::new(),
+            season_id: 0,
+            season_points: 0,
+            settled_season_id: 0,
+        });
+
+        assert!(mining_data.mining_tool_used.len() < 3, "You have reached the maximum mining tool");
+        assert!(mining_data.mining_tool_used.contains(&token_id) == false, "Duplicate mining tool");
+
+        mining_data.mining_tool_used.push(token_id.clone());
+        mining_data.total_mining_power += item.prototype_itemmining_power;
+        self.mining_difficulty.network_mining_power += item.prototype_itemmining_power;
+        mining_data.total_mining_charge_time = self.internal_mining_charge_time(&mining_data.mining_tool_used);
+
+        if mining_data.last_mining_time == 0 || mining_data.mining_tool_used.len() == 1 {
+            mining_data.last_mining_time = env::block_timestamp() as u128;
+        }
+
+        item_instance.is_lock = true;
+
+        self.mining_data_by_account_id.insert(&account_id, &mining_data);
+        self.item_instance_by_token_id.insert(&token_id, &item_instance);
+    }
+}
+
+#[near_bindgen]
+impl JoychiV1 {
+    // Give the points back if the JOY wasn't paid, otherwise mint the reward item
+    #[private]
+    pub fn resolve_redemn_mining_points(&mut self, account_id: AccountId, points: U128, item_id: Option<ItemId>) {
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            let mut mining_data = self.mining_data_by_account_id.get(&account_id).unwrap();
+            mining_data.mining_points += points.0;
+            self.mining_data_by_account_id.insert(&account_id, &mining_data);
+            return;
+        }
+
+        if let Some(item_id) = item_id {
+            let (token_id, promise) = self.internal_mint_item_with_promise(account_id, item_id);
+            promise.then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_REDEMN_ITEM)
+                    .resolve_redemn_item(token_id),
+            );
+        }
+    }
+
+    // Remove the item instance and take it out of the item supply if its NFT wasn't minted
+    #[private]
+    pub fn resolve_redemn_item(&mut self, token_id: TokenId) {
+        if !matches!(env::promise_result(0), PromiseResult::Successful(_)) {
+            log!("Could not mint the redemption item {}", token_id);
+            if let Some(item_instance) = self.item_instance_by_token_id.remove(&token_id) {
+                let mut item_metadata = self.item_metadata_by_id.get(&item_instance.item_id).unwrap();
+                item_metadata.total_supply -= 1;
+                self.item_metadata_by_id.insert(&item_instance.item_id, &item_metadata);
+            }
         }
     }
 }
\ No newline at end of file
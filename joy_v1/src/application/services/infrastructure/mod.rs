@@ -1,4 +1,9 @@
 pub mod impl_item_immidiate;
 pub mod impl_pet;
 pub mod impl_item_factory;
-pub mod impl_staking_and_mining;
\ No newline at end of file
+pub mod impl_staking_and_mining;
+pub mod impl_rental;
+pub mod impl_ft_receiver;
+pub mod impl_nft_receiver;
+pub mod impl_mining_season;
+pub mod impl_ref_swap;
\ No newline at end of file
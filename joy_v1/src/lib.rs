@@ -1,4 +1,6 @@
+use application::services::infrastructure::impl_staking_and_mining::BASIS_POINTS;
 use models::contract::{JoychiV1, JoychiV1Ext, JoychiV1StorageKey};
+use models::staking_and_mining::MiningDifficulty;
 use near_sdk::borsh::BorshSerialize;
 use near_sdk::{
     collections::{LookupMap, UnorderedSet},
@@ -7,6 +9,7 @@ use near_sdk::{
 
 pub mod application;
 pub mod models;
+mod migrate;
 
 #[near_bindgen]
 impl JoychiV1 {
@@ -20,7 +23,7 @@ impl JoychiV1 {
     #[init]
     pub fn new(owner_id: AccountId, nft_addr: AccountId, nft_item_addr: AccountId, ft_addr: AccountId) -> Self {
         Self {
-            owner_id,
+            owner_id: owner_id.clone(),
             nft_address: nft_addr,
             nft_item_address: nft_item_addr,
             manager_address: env::signer_account_id(),
@@ -34,6 +37,9 @@ impl JoychiV1 {
             item_metadata_by_id: LookupMap::new(
                 JoychiV1StorageKey::ItemMetadataById.try_to_vec().unwrap(),
             ),
+            item_instance_by_token_id: LookupMap::new(
+                JoychiV1StorageKey::ItemInstanceByTokenId.try_to_vec().unwrap(),
+            ),
             all_pet_id: UnorderedSet::new(JoychiV1StorageKey::AllPetId.try_to_vec().unwrap()),
             pet_metadata_by_id: LookupMap::new(
                 JoychiV1StorageKey::PetMetadataById.try_to_vec().unwrap(),
@@ -64,11 +70,30 @@ impl JoychiV1 {
             user_staked_pet_count: LookupMap::new(JoychiV1StorageKey::UserStakedPetCountOuter.try_to_vec().unwrap()),
             mining_data_by_account_id: LookupMap::new(JoychiV1StorageKey::MiningDataByAccountId.try_to_vec().unwrap()),
             mining_pool_name: "".to_string(),
-            mining_power_multiplier: 0,
-            charge_of_time_multiplier: 0,
+            // Mining pays x10 the power of the tools by default
+            mining_power_multiplier: 10 * BASIS_POINTS,
+            charge_of_time_multiplier: BASIS_POINTS,
             points_used_per_redemn: 0,
             token_earned_per_redemn: 0,
             price_per_slot: 0,
+            rental_by_pet_id: LookupMap::new(JoychiV1StorageKey::RentalByPetId.try_to_vec().unwrap()),
+            treasury_id: owner_id,
+            redemption_item_ids: Vec::new(),
+            mining_difficulty: MiningDifficulty {
+                difficulty_bps: BASIS_POINTS,
+                network_mining_power: 0,
+                epoch_duration: 0,
+                target_points_per_epoch: 0,
+                epoch_start: 0,
+                epoch_start_network_power: 0,
+                points_issued_in_epoch: 0,
+            },
+            mining_season_by_id: LookupMap::new(JoychiV1StorageKey::MiningSeasonById.try_to_vec().unwrap()),
+            mining_season_count: 0,
+            season_points_by_id: LookupMap::new(JoychiV1StorageKey::SeasonPointsOuter.try_to_vec().unwrap()),
+            pet_owner_stats_by_account: LookupMap::new(JoychiV1StorageKey::PetOwnerStatsByAccount.try_to_vec().unwrap()),
+            ref_exchange: None,
+            marketplace_ids: Vec::new(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,311 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::{
+    collections::{LookupMap, UnorderedSet},
+    env, near_bindgen, AccountId,
+};
+
+use crate::application::services::infrastructure::impl_staking_and_mining::BASIS_POINTS;
+use crate::models::{
+    contract::{BattleMetadata, JoychiV1, JoychiV1Ext, JoychiV1StorageKey},
+    item_factory::{ItemInstance, ItemMetadata, ItemRarity, ItemType},
+    item_immidiate::ItemImmidiateMetadata,
+    pet::{PetEvolution, PetMetadata, PetSpecies},
+    staking_and_mining::{MiningData, MiningDifficulty, NFTInfo, PenaltyReceiver, PoolInfo, PoolMetadata},
+    BattleId, ItemId, PetId, PetSpeciesId, PoolId,
+};
+
+// The item before it became a prototype minted in instances, an item was its own NFT
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldItemMetadata {
+    pub item_id: ItemId,
+    pub item_rarity_amount: u128,
+    pub list_prototype_items_of_rarity: Vec<u128>,
+    pub prototype_item_image: String,
+    pub prototype_item_type: ItemType,
+    pub prototype_item_cooldown_breed_time: u128,
+    pub prototype_item_reduce_breed_fee: u128,
+    pub prototype_item_points: u128,
+    pub prototype_item_rarity: ItemRarity,
+    pub prototype_itemmining_power: u128,
+    pub prototype_itemmining_charge_time: u128,
+    pub owner: AccountId,
+    pub is_lock: bool,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldNFTInfo {
+    pub nft_id: u128,
+    pub owner: AccountId,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldPoolInfo {
+    pub name: String,
+    pub reward_nft_ids: Vec<u128>,
+    pub staking_start_time: u128,
+    pub staking_end_time: u128,
+    pub max_slot_in_pool: u128,
+    pub token_reward_per_slot: u128,
+    pub max_slot_per_wallet: u128,
+    pub total_staked_slot: u128,
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldPoolMetadata {
+    pub pool_id: PoolId,
+    pub price_per_slot: u128,
+    pub pool_info: OldPoolInfo,
+    pub staked_pets: Vec<OldNFTInfo>,
+}
+
+// The mining data before the tools were escrowed item instances
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldMiningData {
+    pub account_id: Option<AccountId>,
+    pub mining_points: u128,
+    pub total_mining_power: u128,
+    pub total_mining_charge_time: u128,
+    pub last_mining_time: u128,
+    pub mining_tool_used: Vec<u128>,
+}
+
+// The contract state before item instances, rentals, funded pools, mining seasons and Ref purchases were added
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldJoychiV1 {
+    pub owner_id: AccountId,
+    pub nft_address: AccountId,
+    pub nft_item_address: AccountId,
+    pub manager_address: AccountId,
+    pub total_score: u128,
+    pub ft_address: AccountId,
+    pub all_item_immidiate_id: UnorderedSet<ItemId>,
+    pub item_immidiate_metadata_by_id: LookupMap<ItemId, ItemImmidiateMetadata>,
+    pub all_item_id: UnorderedSet<ItemId>,
+    pub item_metadata_by_id: LookupMap<ItemId, OldItemMetadata>,
+    pub all_pet_id: UnorderedSet<PetId>,
+    pub pet_metadata_by_id: LookupMap<PetId, PetMetadata>,
+    pub all_battle_id: UnorderedSet<BattleId>,
+    pub battle_metadata_by_id: LookupMap<BattleId, BattleMetadata>,
+    pub all_pet_species_id: UnorderedSet<PetSpeciesId>,
+    pub pet_species_metadata_by_id: LookupMap<PetSpeciesId, PetSpecies>,
+    pub pet_evolution_metadata_by_id: LookupMap<PetId, Vec<PetEvolution>>,
+    pub pool_metadata_by_id: LookupMap<PoolId, OldPoolMetadata>,
+    pub all_pool_id: UnorderedSet<PoolId>,
+    pub user_staked_pet_count: LookupMap<AccountId, LookupMap<PoolId, u64>>,
+    pub mining_data_by_account_id: LookupMap<AccountId, OldMiningData>,
+    pub mining_pool_name: String,
+    pub mining_power_multiplier: u128,
+    pub charge_of_time_multiplier: u128,
+    pub points_used_per_redemn: u128,
+    pub token_earned_per_redemn: u128,
+    pub price_per_slot: u128,
+}
+
+#[near_bindgen]
+impl JoychiV1 {
+    /*
+        migrate the state of a contract deployed before item instances, rentals, funded pools,
+        mining seasons and Ref purchases. The items become prototypes and every item gets an
+        instance under its old token id, mining tools get tool_durability. The pools are funded
+        as the old contract paid their rewards from its balance, the pets staked in them earn
+        from the start of the pool at x1. The mining data can't be listed, the owner moves it
+        with migrate_mining_data before these accounts mine again.
+    */
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate(tool_durability: u128) -> Self {
+        let old_state: OldJoychiV1 = env::state_read().expect("No state to migrate");
+        assert!(tool_durability > 0, "Mining tool durability must be greater than 0");
+
+        let mut this = Self {
+            owner_id: old_state.owner_id.clone(),
+            nft_address: old_state.nft_address,
+            nft_item_address: old_state.nft_item_address,
+            manager_address: old_state.manager_address,
+            total_score: old_state.total_score,
+            ft_address: old_state.ft_address,
+            all_item_immidiate_id: old_state.all_item_immidiate_id,
+            item_immidiate_metadata_by_id: old_state.item_immidiate_metadata_by_id,
+            all_item_id: old_state.all_item_id,
+            //the items and pools are rewritten below under the same storage prefix
+            item_metadata_by_id: LookupMap::new(JoychiV1StorageKey::ItemMetadataById.try_to_vec().unwrap()),
+            item_instance_by_token_id: LookupMap::new(
+                JoychiV1StorageKey::ItemInstanceByTokenId.try_to_vec().unwrap(),
+            ),
+            all_pet_id: old_state.all_pet_id,
+            pet_metadata_by_id: old_state.pet_metadata_by_id,
+            all_battle_id: old_state.all_battle_id,
+            battle_metadata_by_id: old_state.battle_metadata_by_id,
+            all_pet_species_id: old_state.all_pet_species_id,
+            pet_species_metadata_by_id: old_state.pet_species_metadata_by_id,
+            pet_evolution_metadata_by_id: old_state.pet_evolution_metadata_by_id,
+            pool_metadata_by_id: LookupMap::new(JoychiV1StorageKey::PoolMetadataById.try_to_vec().unwrap()),
+            all_pool_id: old_state.all_pool_id,
+            user_staked_pet_count: old_state.user_staked_pet_count,
+            mining_data_by_account_id: LookupMap::new(
+                JoychiV1StorageKey::MiningDataByAccountId.try_to_vec().unwrap(),
+            ),
+            mining_pool_name: old_state.mining_pool_name,
+            mining_power_multiplier: old_state.mining_power_multiplier,
+            charge_of_time_multiplier: old_state.charge_of_time_multiplier,
+            points_used_per_redemn: old_state.points_used_per_redemn,
+            token_earned_per_redemn: old_state.token_earned_per_redemn,
+            price_per_slot: old_state.price_per_slot,
+            rental_by_pet_id: LookupMap::new(JoychiV1StorageKey::RentalByPetId.try_to_vec().unwrap()),
+            treasury_id: old_state.owner_id,
+            redemption_item_ids: Vec::new(),
+            mining_difficulty: MiningDifficulty {
+                difficulty_bps: BASIS_POINTS,
+                network_mining_power: 0,
+                epoch_duration: 0,
+                target_points_per_epoch: 0,
+                epoch_start: 0,
+                epoch_start_network_power: 0,
+                points_issued_in_epoch: 0,
+            },
+            mining_season_by_id: LookupMap::new(JoychiV1StorageKey::MiningSeasonById.try_to_vec().unwrap()),
+            mining_season_count: 0,
+            season_points_by_id: LookupMap::new(JoychiV1StorageKey::SeasonPointsOuter.try_to_vec().unwrap()),
+            pet_owner_stats_by_account: LookupMap::new(
+                JoychiV1StorageKey::PetOwnerStatsByAccount.try_to_vec().unwrap(),
+            ),
+            ref_exchange: None,
+            marketplace_ids: Vec::new(),
+        };
+
+        for item_id in this.all_item_id.to_vec() {
+            let old_item = match old_state.item_metadata_by_id.get(&item_id) {
+                Some(old_item) => old_item,
+                None => continue,
+            };
+            let max_durability = if old_item.prototype_item_type == ItemType::MineTool {
+                tool_durability
+            } else {
+                0
+            };
+
+            // The old NFT of the item was minted with the item id as token id
+            let item_instance = ItemInstance {
+                token_id: item_id.to_string(),
+                item_id,
+                serial: 0,
+                is_lock: false,
+                durability: max_durability,
+                minted_at: 0,
+            };
+            this.item_instance_by_token_id.insert(&item_instance.token_id, &item_instance);
+
+            let item = ItemMetadata {
+                item_id,
+                item_rarity_amount: old_item.item_rarity_amount,
+                list_prototype_items_of_rarity: old_item.list_prototype_items_of_rarity,
+                prototype_item_image: old_item.prototype_item_image,
+                prototype_item_type: old_item.prototype_item_type,
+                prototype_item_cooldown_breed_time: old_item.prototype_item_cooldown_breed_time,
+                prototype_item_reduce_breed_fee: old_item.prototype_item_reduce_breed_fee,
+                prototype_item_points: old_item.prototype_item_points,
+                prototype_item_rarity: old_item.prototype_item_rarity,
+                prototype_itemmining_power: old_item.prototype_itemmining_power,
+                prototype_itemmining_charge_time: old_item.prototype_itemmining_charge_time,
+                prototype_item_max_durability: max_durability,
+                prototype_item_repair_price: 0,
+                total_supply: 0,
+            };
+            this.item_metadata_by_id.insert(&item_id, &item);
+        }
+
+        for pool_id in this.all_pool_id.to_vec() {
+            let old_pool = match old_state.pool_metadata_by_id.get(&pool_id) {
+                Some(old_pool) => old_pool,
+                None => continue,
+            };
+            let old_info = old_pool.pool_info;
+
+            let pool_info = PoolInfo {
+                name: old_info.name,
+                reward_nft_ids: old_info.reward_nft_ids,
+                staking_start_time: old_info.staking_start_time,
+                staking_end_time: old_info.staking_end_time,
+                max_slot_in_pool: old_info.max_slot_in_pool,
+                token_reward_per_slot: old_info.token_reward_per_slot,
+                max_slot_per_wallet: old_info.max_slot_per_wallet,
+                total_staked_slot: old_info.total_staked_slot,
+                eligibility: Default::default(),
+                lock_terms: Vec::new(),
+                early_exit_penalty_bps: 0,
+                penalty_receiver: PenaltyReceiver::Treasury,
+            };
+            let staked_pets = old_pool
+                .staked_pets
+                .into_iter()
+                .map(|old_nft_info| NFTInfo {
+                    nft_id: old_nft_info.nft_id,
+                    owner: old_nft_info.owner,
+                    staked_at: pool_info.staking_start_time,
+                    reward_claimed: 0,
+                    lock_until: 0,
+                    multiplier_bps: BASIS_POINTS,
+                    penalty_share_debt: 0,
+                })
+                .collect();
+            let total_reward = this.internal_pool_total_reward(&pool_info);
+
+            let pool = PoolMetadata {
+                pool_id,
+                price_per_slot: old_pool.price_per_slot,
+                pool_info,
+                staked_pets,
+                total_reward_distributed: 0,
+                total_reward_funded: total_reward,
+                total_reward_withdrawn: 0,
+                is_active: true,
+                penalty_share_per_slot: 0,
+            };
+            this.pool_metadata_by_id.insert(&pool_id, &pool);
+        }
+
+        this
+    }
+
+    /*
+        move the mining data of accounts from the old contract, in batches to stay under the gas limit.
+        the mining points are kept, the old tools weren't escrowed so they are added again
+        with nft_transfer_call. Accounts already moved are skipped.
+    */
+    pub fn migrate_mining_data(&mut self, account_ids: Vec<AccountId>) -> u32 {
+        assert!(
+            self.owner_id == env::signer_account_id(),
+            "You're not permission"
+        );
+
+        let prefix = JoychiV1StorageKey::MiningDataByAccountId.try_to_vec().unwrap();
+        let mut migrated = 0;
+        for account_id in account_ids {
+            let mut key = prefix.clone();
+            key.extend(account_id.try_to_vec().unwrap());
+
+            let old_mining_data = match env::storage_read(&key)
+                .and_then(|raw| OldMiningData::try_from_slice(&raw).ok())
+            {
+                Some(old_mining_data) => old_mining_data,
+                None => continue,
+            };
+
+            let mining_data = MiningData {
+                account_id: Some(account_id.clone()),
+                mining_points: old_mining_data.mining_points,
+                total_mining_power: 0,
+                total_mining_charge_time: 0,
+                last_mining_time: 0,
+                mining_tool_used: Vec::new(),
+                season_id: 0,
+                season_points: 0,
+                settled_season_id: 0,
+            };
+            self.mining_data_by_account_id.insert(&account_id, &mining_data);
+            migrated += 1;
+        }
+
+        migrated
+    }
+}
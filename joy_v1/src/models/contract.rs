@@ -8,10 +8,15 @@ use near_sdk::{
     AccountId, PanicOnDefault,
 };
 
-use super::item_factory::ItemMetadata;
-use super::pet::{PetEvolution, PetSpecies};
-use super::staking_and_mining::{MiningData, PoolMetadata};
-use super::{PetSpeciesId, PoolId};
+use near_contract_standards::non_fungible_token::TokenId;
+
+use super::item_factory::{ItemInstance, ItemMetadata};
+use super::pet::{PetEvolution, PetOwnerStats, PetSpecies};
+use super::ref_swap::RefExchange;
+use super::mining_season::MiningSeason;
+use super::rental::RentalOffer;
+use super::staking_and_mining::{MiningData, MiningDifficulty, PoolMetadata};
+use super::{PetSpeciesId, PoolId, SeasonId};
 use super::{item_immidiate::ItemImmidiateMetadata, pet::PetMetadata, BattleId, ItemId, PetId};
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -63,6 +68,8 @@ pub struct JoychiV1 {
 
     pub item_metadata_by_id: LookupMap<ItemId, ItemMetadata>,
 
+    pub item_instance_by_token_id: LookupMap<TokenId, ItemInstance>,
+
     pub all_pet_id: UnorderedSet<PetId>,
 
     pub pet_metadata_by_id: LookupMap<PetId, PetMetadata>,
@@ -96,6 +103,26 @@ pub struct JoychiV1 {
     pub token_earned_per_redemn: u128,
 
     pub price_per_slot: u128,
+
+    pub rental_by_pet_id: LookupMap<PetId, RentalOffer>,
+
+    pub treasury_id: AccountId,
+
+    pub redemption_item_ids: Vec<ItemId>,
+
+    pub mining_difficulty: MiningDifficulty,
+
+    pub mining_season_by_id: LookupMap<SeasonId, MiningSeason>,
+
+    pub mining_season_count: SeasonId,
+
+    pub season_points_by_id: LookupMap<SeasonId, LookupMap<AccountId, u128>>,
+
+    pub pet_owner_stats_by_account: LookupMap<AccountId, PetOwnerStats>,
+
+    pub ref_exchange: Option<RefExchange>,
+
+    pub marketplace_ids: Vec<AccountId>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
@@ -145,5 +172,11 @@ pub enum JoychiV1StorageKey {
     LastMiningTime,
     MiningToolUsed,
     IsItemLock,
-    MiningDataByAccountId
+    MiningDataByAccountId,
+    ItemInstanceByTokenId,
+    RentalByPetId,
+    MiningSeasonById,
+    SeasonPointsOuter,
+    SeasonPointsInner { season_id: SeasonId },
+    PetOwnerStatsByAccount,
 }
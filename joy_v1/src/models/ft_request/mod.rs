@@ -1 +1,2 @@
 pub mod external;
+pub mod receiver;
\ No newline at end of file
@@ -0,0 +1,15 @@
+use near_sdk::serde::{Deserialize, Serialize};
+
+use near_contract_standards::non_fungible_token::TokenId;
+
+use crate::models::{PetId, PoolId, SeasonId};
+
+// The msg of ft_transfer_call, tells what the JOY sent to the contract is paying for
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum FtOnTransferMsg {
+    RentPet { pet_id: PetId },
+    FundPool { pool_id: PoolId },
+    RepairTool { token_id: TokenId },
+    FundSeason { season_id: SeasonId },
+}
@@ -1,10 +1,11 @@
+use near_contract_standards::non_fungible_token::TokenId;
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
     serde::{Deserialize, Serialize},
     AccountId,
 };
 
-use super::ItemId;
+use super::{nft_request::external::TokenType, ItemId};
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
@@ -20,8 +21,20 @@ pub struct ItemMetadata {
     pub prototype_item_rarity: ItemRarity,
     pub prototype_itemmining_power: u128,
     pub prototype_itemmining_charge_time: u128,
-    pub owner: AccountId,
+    pub prototype_item_max_durability: u128,
+    pub prototype_item_repair_price: u128,
+    pub total_supply: u64,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct ItemInstance {
+    pub token_id: TokenId,
+    pub item_id: ItemId,
+    pub serial: u64,
     pub is_lock: bool,
+    pub durability: u128,
+    pub minted_at: u64,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug)]
@@ -41,6 +54,16 @@ pub enum ItemType {
     MineTool,
 }
 
+impl ItemType {
+    // The item NFT token type the instances of this item type are minted with
+    pub fn token_type(&self) -> TokenType {
+        match self {
+            ItemType::Normal => TokenType::Consumable,
+            ItemType::MineTool => TokenType::Tool,
+        }
+    }
+}
+
 pub trait ItemFeature {
     fn create_item(
         &mut self,
@@ -52,6 +75,8 @@ pub trait ItemFeature {
         prototype_item_rarity: ItemRarity,
         prototype_itemmining_power: u128,
         prototype_itemmining_charge_time: u128,
+        prototype_item_max_durability: u128,
+        prototype_item_repair_price: u128,
     ) -> ItemMetadata;
 
     fn edit_item(
@@ -64,13 +89,17 @@ pub trait ItemFeature {
         prototype_item_rarity: ItemRarity,
         prototype_itemmining_power: u128,
         prototype_itemmining_charge_time: u128,
+        prototype_item_max_durability: u128,
+        prototype_item_repair_price: u128,
     );
 
-    fn mint_item_for_user(&mut self, to_addr: AccountId, item_id: ItemId);
+    fn mint_item_for_user(&mut self, to_addr: AccountId, item_id: ItemId) -> TokenId;
 }
 
 pub trait ItemEnum {
     fn get_all_item_metadata(&self, start: Option<u32>, limit: Option<u32>) -> Vec<ItemMetadata>;
 
     fn get_item_by_item_id(&self, item_id: ItemId) -> ItemMetadata;
+
+    fn get_item_instance_by_token_id(&self, token_id: TokenId) -> ItemInstance;
 }
\ No newline at end of file
@@ -0,0 +1,64 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+use super::SeasonId;
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MiningSeason {
+    pub season_id: SeasonId,
+    pub start_time: u128,
+    pub end_time: u128,
+    // Multiplier of the mined points in basis points, halved from the previous season when halving is on
+    pub emission_bps: u128,
+    // Share of the mining points kept when the season ends, 0 resets them
+    pub carry_over_bps: u128,
+    // JOY the miners ranked 1, 2, ... can claim once the season ended
+    pub rank_rewards: Vec<U128>,
+    pub total_reward_funded: u128,
+    // JOY of the ranks nobody reached, swept by the owner once the season ended
+    pub total_reward_withdrawn: u128,
+    // Top miners of the season, frozen when the season ends
+    pub leaderboard: Vec<SeasonRank>,
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SeasonRank {
+    pub account_id: AccountId,
+    pub points: u128,
+    pub claimed: bool,
+}
+
+pub trait MiningSeasons {
+    // Seasons follow each other, the first one has an emission of x1
+    fn create_mining_season(
+        &mut self,
+        start_time: u128,
+        end_time: u128,
+        rank_rewards: Vec<U128>,
+        carry_over_bps: u128,
+        halving: bool,
+    ) -> MiningSeason;
+
+    // Pay the reward of the signer's rank once the season ended
+    fn claim_season_reward(&mut self, season_id: SeasonId) -> U128;
+
+    // Withdraw the rewards of the ranks no miner reached once the season ended
+    fn owner_withdraw_unclaimed_season_reward(&mut self, season_id: SeasonId) -> U128;
+}
+
+pub trait MiningSeasonsEnum {
+    fn get_mining_season(&self, season_id: SeasonId) -> Option<MiningSeason>;
+
+    fn get_current_mining_season(&self) -> Option<MiningSeason>;
+
+    fn get_season_leaderboard(&self, season_id: SeasonId) -> Vec<SeasonRank>;
+
+    // The points an account mined in a season, kept whatever its rank
+    fn get_season_points(&self, season_id: SeasonId, account_id: AccountId) -> u128;
+}
@@ -2,8 +2,12 @@ pub mod contract;
 pub mod ft_request;
 pub mod item_factory;
 pub mod item_immidiate;
+pub mod mining_season;
 pub mod nft_request;
 pub mod pet;
+pub mod ref_request;
+pub mod ref_swap;
+pub mod rental;
 pub mod staking_and_mining;
 
 pub type ItemId = u64;
@@ -12,3 +16,4 @@ pub type AccountId = String;
 pub type BattleId = u64;
 pub type PetSpeciesId = u64;
 pub type PoolId = u64;
+pub type SeasonId = u64;
@@ -20,6 +20,10 @@ pub trait PetCrossCall {
 
     fn update_medatada_pet(&mut self, token_id: String, pet_attribute: PetAttribute);
     fn update_token_metadata(&mut self, token_id: String, token_metadata: TokenMetadata);
+    fn nft_token(&self, token_id: String) -> Option<JsonToken>;
+    fn nft_lock(&mut self, token_id: String, owner_id: AccountId, reason: String);
+    fn nft_unlock(&mut self, token_id: String);
+    fn nft_transfer(&mut self, receiver_id: AccountId, token_id: String, approval_id: Option<u64>, memo: Option<String>);
 }
 
 #[ext_contract(cross_item_nft)]
@@ -29,11 +33,15 @@ pub trait ItemCrossCall {
         token_id: String,
         metadata: TokenMetadata,
         receiver_id: AccountId,
-        //we add an optional parameter for perpetual royalties
+        token_type: Option<TokenType>,
     );
 
     fn update_medatada_item(&mut self, token_id: String, item_attribute: ItemAttribute);
     fn update_token_metadata(&mut self, token_id: String, token_metadata: TokenMetadata);
+    fn nft_token(&self, token_id: String) -> Option<JsonToken>;
+    fn nft_lock(&mut self, token_id: String, owner_id: AccountId, reason: String);
+    fn nft_unlock(&mut self, token_id: String);
+    fn nft_transfer(&mut self, receiver_id: AccountId, token_id: String, approval_id: Option<u64>, memo: Option<String>);
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
@@ -60,6 +68,15 @@ pub struct ItemAttribute {
     pub mining_charge_time: u128,
 }
 
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TokenType {
+    Tool,
+    Consumable,
+    Cosmetic,
+    EvolutionItem,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct TokenMetadata {
@@ -75,4 +92,12 @@ pub struct TokenMetadata {
     pub extra: Option<String>, // anything extra the NFT wants to store on-chain. Can be stringified JSON.
     pub reference: Option<String>, // URL to an off-chain JSON file with more info.
     pub reference_hash: Option<Base64VecU8>, // Base64-encoded sha256 hash of JSON from reference field. Required if `reference` is included.
-}
\ No newline at end of file
+}
+
+//The Json token returned by the nft_token view of the NFT contracts
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct JsonToken {
+    pub token_id: String,
+    pub owner_id: AccountId,
+}
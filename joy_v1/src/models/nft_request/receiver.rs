@@ -0,0 +1,11 @@
+use near_sdk::serde::{Deserialize, Serialize};
+
+use crate::models::PoolId;
+
+// The msg of nft_transfer_call, tells what the NFT sent to the contract is used for
+#[derive(Deserialize, Serialize)]
+#[serde(crate = "near_sdk::serde")]
+pub enum NftOnTransferMsg {
+    Stake { pool_id: PoolId, lock_term: Option<usize> },
+    AddMiningTool,
+}
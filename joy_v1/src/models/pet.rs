@@ -58,9 +58,20 @@ pub struct PetEvolution {
     pub next_evolution_level: u128,
 }
 
+// The pets an account owns now and the ones it has created
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PetOwnerStats {
+    pub pet_count: u64,
+    pub pets_created: u64,
+}
+
 pub trait PetFeature {
     fn set_manager(&mut self, manager_addr: AccountId);
 
+    // Marketplaces that hold pets in escrow during auctions, their pets keep the seller as owner
+    fn set_marketplaces(&mut self, marketplace_ids: Vec<AccountId>);
+
     fn create_pet(&mut self, name: String) -> PetMetadata;
 
     fn change_name_pet(&mut self, pet_id: PetId, name: String);
@@ -98,6 +109,9 @@ pub trait PetFeature {
     fn delegate_update_metadata(&mut self, pet_id: PetId, token_metadata: TokenMetadata);
 
     fn check_evol_pet_if_needed(&mut self, pet_id: PetId);
+
+    // Read the owner of the pet from the pet NFT contract after a trade
+    fn sync_pet_owner(&mut self, pet_id: PetId);
 }
 
 pub trait PetEnum {
@@ -122,4 +136,8 @@ pub trait PetEnum {
     fn get_pet_image(&self, pet_id: PetId) -> String;
 
     fn get_pet_evolution_phase(&self, pet_id: PetId, current_evo_phase: u128) -> u128;
+
+    fn get_pet_owner_stats(&self, account_id: AccountId) -> PetOwnerStats;
+
+    fn get_marketplaces(&self) -> Vec<AccountId>;
 }
@@ -0,0 +1,36 @@
+use near_sdk::{
+    ext_contract,
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+// A swap in a Ref pool, amount_in is None to swap the output of the previous action
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SwapAction {
+    pub pool_id: u64,
+    pub token_in: AccountId,
+    pub amount_in: Option<U128>,
+    pub token_out: AccountId,
+    pub min_amount_out: U128,
+}
+
+#[ext_contract(cross_ref_exchange)]
+pub trait RefExchangeCrossCall {
+    fn swap(&mut self, actions: Vec<SwapAction>, referral_id: Option<AccountId>) -> U128;
+    fn withdraw(&mut self, token_id: AccountId, amount: U128, unregister: Option<bool>);
+}
+
+#[ext_contract(cross_wnear)]
+pub trait WrapNearCrossCall {
+    fn near_deposit(&mut self);
+    fn near_withdraw(&mut self, amount: U128);
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> U128;
+}
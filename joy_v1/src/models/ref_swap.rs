@@ -0,0 +1,29 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    serde::{Deserialize, Serialize},
+    AccountId, Promise,
+};
+
+use super::{ItemId, PetId};
+
+// The Ref pool of wNEAR and JOY purchases paid in NEAR are swapped in
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RefExchange {
+    pub ref_exchange_id: AccountId,
+    pub wnear_id: AccountId,
+    pub pool_id: u64,
+}
+
+pub trait RefSwap {
+    // The game must be registered on wNEAR, JOY and Ref, None turns the NEAR purchases off
+    fn set_ref_exchange(&mut self, ref_exchange: Option<RefExchange>);
+
+    // Pay buy_item_immidiate with the attached NEAR swapped to JOY on Ref.
+    // The item is given once the JOY is received, the extra JOY goes to the buyer and the NEAR is refunded if the swap fails
+    fn buy_item_immidiate_with_near(&mut self, pet_id: PetId, item_id: ItemId) -> Promise;
+}
+
+pub trait RefSwapEnum {
+    fn get_ref_exchange(&self) -> Option<RefExchange>;
+}
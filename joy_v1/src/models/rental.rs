@@ -0,0 +1,39 @@
+use near_sdk::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    json_types::U128,
+    serde::{Deserialize, Serialize},
+    AccountId,
+};
+
+use super::PetId;
+
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct RentalOffer {
+    pub pet_id: PetId,
+    pub owner_id: AccountId,
+    // Rental duration in nanoseconds
+    pub duration: u128,
+    // Fee in JOY paid to the owner by the renter
+    pub fee: U128,
+    pub renter_id: Option<AccountId>,
+    pub rented_at: u128,
+    pub expires_at: u128,
+}
+
+pub trait Rental {
+    // Offer a pet for rent, the renter is delegated attack and feed rights until the rental expires
+    fn offer_pet_for_rent(&mut self, pet_id: PetId, duration: u128, fee: U128) -> RentalOffer;
+
+    fn cancel_rental_offer(&mut self, pet_id: PetId);
+
+    // Unlock the pet NFT and clean the delegation up once the rental expired (anyone can call it)
+    fn end_rental(&mut self, pet_id: PetId);
+
+    // Check if the user can attack with and feed the pet: the delegated users, and the owner when the pet isn't rented
+    fn check_role_play_pet(&self, pet_id: PetId, user_id: AccountId) -> bool;
+}
+
+pub trait RentalEnum {
+    fn get_rental_by_pet_id(&self, pet_id: PetId) -> Option<RentalOffer>;
+}
@@ -1,11 +1,12 @@
 use near_contract_standards::non_fungible_token::TokenId;
 use near_sdk::{
     AccountId,
+    json_types::U128,
     borsh::{self, BorshDeserialize, BorshSerialize},
     serde::{Deserialize, Serialize},
 };
 
-use super::{PetId, PoolId};
+use super::{ItemId, PetId, PoolId, SeasonId};
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
@@ -18,7 +19,12 @@ pub struct PetCountUser {
 #[serde(crate = "near_sdk::serde")]
 pub struct NFTInfo {
     pub nft_id: u128,
-    pub owner: AccountId
+    pub owner: AccountId,
+    pub staked_at: u128,
+    pub reward_claimed: u128,
+    pub lock_until: u128,
+    pub multiplier_bps: u128,
+    pub penalty_share_debt: u128,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
@@ -28,6 +34,22 @@ pub struct PoolMetadata {
     pub price_per_slot: u128,
     pub pool_info: PoolInfo,
     pub staked_pets: Vec<NFTInfo>,
+    pub total_reward_distributed: u128,
+    pub total_reward_funded: u128,
+    pub total_reward_withdrawn: u128,
+    pub is_active: bool,
+    pub penalty_share_per_slot: u128,
+}
+
+// The JOY a staking pool holds for its rewards
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolBalance {
+    pub total_reward: U128,
+    pub funded: U128,
+    pub distributed: U128,
+    pub withdrawn: U128,
+    pub remaining: U128,
 }
 
 
@@ -42,6 +64,44 @@ pub struct PoolInfo {
     pub token_reward_per_slot: u128,
     pub max_slot_per_wallet: u128,
     pub total_staked_slot: u128,
+    pub eligibility: PoolEligibility,
+    pub lock_terms: Vec<LockTerm>,
+    pub early_exit_penalty_bps: u128,
+    pub penalty_receiver: PenaltyReceiver,
+}
+
+// A stake can be locked for longer to earn more, the multiplier is in basis points (10000 = x1)
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct LockTerm {
+    pub duration: u128,
+    pub multiplier_bps: u128,
+}
+
+// Who gets the reward forfeited by an early unstake
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum PenaltyReceiver {
+    Treasury,
+    Stakers,
+}
+
+// Optional rules a pet must match to be staked in a pool
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Default)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolEligibility {
+    pub species_ids: Option<Vec<u128>>,
+    pub min_level: Option<u128>,
+    pub min_evolution_phase: Option<u128>,
+    pub min_star: Option<u64>,
+    pub whitelist: Option<Vec<AccountId>>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct PoolEligibilityCheck {
+    pub eligible: bool,
+    pub reasons: Vec<String>,
 }
 
 #[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
@@ -52,28 +112,81 @@ pub struct MiningData {
     pub total_mining_power: u128,
     pub total_mining_charge_time: u128,
     pub last_mining_time: u128,
-    pub mining_tool_used: Vec<u128>,
+    pub mining_tool_used: Vec<TokenId>,
+    pub season_id: SeasonId,
+    pub season_points: u128,
+    // The last season whose carry over was applied to the mining points
+    pub settled_season_id: SeasonId,
+}
+
+// The points the next mining gives and when the tools are charged
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MiningPreview {
+    pub mining_points: u128,
+    pub charge_time: u128,
+    pub ready_at: u128,
+    pub is_ready: bool,
+}
+
+// The global difficulty, rescaled every epoch so the points mined by the network stay near the target
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MiningDifficulty {
+    pub difficulty_bps: u128,
+    pub network_mining_power: u128,
+    pub epoch_duration: u128,
+    pub target_points_per_epoch: u128,
+    pub epoch_start: u128,
+    pub epoch_start_network_power: u128,
+    pub points_issued_in_epoch: u128,
+}
+
+// The part of the network mining power an account holds and what it can expect to mine per epoch
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(crate = "near_sdk::serde")]
+pub struct MiningShare {
+    pub mining_power: u128,
+    pub network_mining_power: u128,
+    pub share_bps: u128,
+    pub estimated_points_per_epoch: u128,
 }
 
 pub trait StakingAndMining {
-    fn create_new_staking_pool(&mut self, name: String, reward_nft_ids: Vec<u128>, staking_start_time: u128, staking_end_time: u128, max_slot_in_pool: u128, token_reward_per_slot: u128, max_slot_per_wallet: u128) -> PoolMetadata;
-    fn stake(&mut self, nft_id: PetId, pool_id: PoolId) -> PoolMetadata;
+    fn create_new_staking_pool(&mut self, name: String, reward_nft_ids: Vec<u128>, staking_start_time: u128, staking_end_time: u128, max_slot_in_pool: u128, token_reward_per_slot: u128, max_slot_per_wallet: u128, eligibility: Option<PoolEligibility>) -> PoolMetadata;
     fn un_stake(&mut self, nft_id: PetId, pool_id: PoolId);
-    fn add_mining_tool(&mut self, tool_id: u64);
-    fn remove_mining_tool(&mut self, tool_id: u64);
+    fn claim_staking_reward(&mut self, nft_id: PetId, pool_id: PoolId) -> U128;
+    fn set_pool_lock_terms(&mut self, pool_id: PoolId, lock_terms: Vec<LockTerm>, early_exit_penalty_bps: u128, penalty_receiver: PenaltyReceiver);
+    fn set_treasury(&mut self, treasury_id: AccountId);
+    fn remove_mining_tool(&mut self, token_id: TokenId);
     fn mining(&mut self);
     fn redemn_mining_points(&mut self);
     fn owner_withdraw_redundant_token(&mut self, pool_id: PoolId);
     fn configure_mining_pool(&mut self, name: String, mining_power_multiplier: u128, charge_of_time_multiplier: u128);
+    fn configure_mining_difficulty(&mut self, epoch_duration: u128, target_points_per_epoch: u128);
     fn set_mining_points_used_per_redemn(&mut self, points: u128);
     fn set_token_earned_per_redemn(&mut self, token: u128);
+    fn set_redemption_items(&mut self, item_ids: Vec<ItemId>);
     fn set_price_per_slot(&mut self, price_per_slot: u128);
-    fn caculate_charge_of_time(&mut self, number_of_tools: u128);
-    fn remove_item_from_list_tool(&mut self, value: u128);
+    fn caculate_charge_of_time(&self, number_of_tools: u128) -> u128;
 }
 
 pub trait StakingAndMiningEnum {
+    fn get_pool_metadata_by_id(&self, pool_id: PoolId) -> Option<PoolMetadata>;
+
+    fn get_pending_staking_reward(&self, nft_id: PetId, pool_id: PoolId) -> U128;
+
+    fn get_pool_balance(&self, pool_id: PoolId) -> PoolBalance;
 
+    fn check_pool_eligibility(&self, nft_id: PetId, pool_id: PoolId) -> PoolEligibilityCheck;
 
     fn get_mining_data_by_account_id(&self, account_id: AccountId) -> MiningData;
+
+    fn preview_mining(&self, account_id: AccountId) -> MiningPreview;
+
+    fn get_redemption_items(&self) -> Vec<ItemId>;
+
+    fn get_mining_difficulty(&self) -> MiningDifficulty;
+
+    fn get_mining_share(&self, account_id: AccountId) -> MiningShare;
 }
\ No newline at end of file
@@ -0,0 +1,3 @@
+cargo make clean
+cargo make build
+cargo make dev-deploy
\ No newline at end of file
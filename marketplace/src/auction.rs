@@ -0,0 +1,291 @@
+use crate::*;
+use near_sdk::log;
+
+//bids placed this close to the end of an english auction push the end back, in nanoseconds (5 minutes)
+const ANTI_SNIPING_WINDOW: u64 = 5 * 60 * 1_000_000_000;
+
+pub type AuctionId = u64;
+
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(crate = "near_sdk::serde")]
+pub enum AuctionKind {
+    //the price goes up with every bid, the highest bidder wins at the end
+    English,
+    //the price goes down from the start price to the reserve price, the first bidder wins
+    Dutch,
+}
+
+//struct that holds important information about each auction on the market
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Auction {
+    pub auction_id: U64,
+    pub kind: AuctionKind,
+    //owner of the escrowed token
+    pub owner_id: AccountId,
+    //nft contract where the token was minted
+    pub nft_contract_id: AccountId,
+    //actual token ID in escrow
+    pub token_id: TokenId,
+    //english: lowest first bid, dutch: price when the auction starts
+    pub start_price: U128,
+    //english: lowest winning bid, dutch: price when the auction ends
+    pub reserve_price: U128,
+    //english: how much a bid must beat the highest bid by
+    pub min_bid_increment: U128,
+    //when bids are accepted, Unix epoch in nanoseconds
+    pub start_at: U64,
+    pub end_at: U64,
+    pub highest_bidder: Option<AccountId>,
+    pub highest_bid: U128,
+}
+
+//the msg passed to nft_transfer_call by the seller to start an auction
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct AuctionArgs {
+    pub kind: AuctionKind,
+    pub start_price: U128,
+    pub reserve_price: U128,
+    pub min_bid_increment: Option<U128>,
+    pub start_at: Option<U64>,
+    pub end_at: U64,
+}
+
+#[near_bindgen]
+impl Contract {
+    //settle an auction after it ended (anyone can call it)
+    pub fn settle_auction(&mut self, auction_id: U64) {
+        let auction = self.auctions.get(&auction_id.0).expect("No auction");
+        assert!(
+            env::block_timestamp() >= auction.end_at.0,
+            "Auction has not ended yet"
+        );
+
+        let auction = self.internal_remove_auction(auction_id.0);
+        match auction.highest_bidder.clone() {
+            //the highest bid is sold if it reached the reserve price
+            Some(winner_id) if auction.highest_bid.0 >= auction.reserve_price.0 => {
+                let price = auction.highest_bid.0;
+                self.internal_settle_auction(auction, winner_id, price);
+            }
+            //otherwise the bid is refunded and the token goes back to the owner
+            Some(bidder_id) => {
+                self.internal_refund(bidder_id, auction.highest_bid.0);
+                self.internal_return_auction_token(auction);
+            }
+            None => self.internal_return_auction_token(auction),
+        }
+    }
+
+    //cancel an auction that didn't receive any bid and get the token back
+    #[payable]
+    pub fn cancel_auction(&mut self, auction_id: U64) {
+        //assert that the user has attached exactly 1 yoctoNEAR (for security reasons)
+        assert_one_yocto();
+
+        let auction = self.auctions.get(&auction_id.0).expect("No auction");
+        assert_eq!(
+            env::predecessor_account_id(),
+            auction.owner_id,
+            "Must be auction owner"
+        );
+        assert!(
+            auction.highest_bidder.is_none(),
+            "Auction already has a bid"
+        );
+
+        let auction = self.internal_remove_auction(auction_id.0);
+        self.internal_return_auction_token(auction);
+    }
+
+    /*
+        private method used to resolve the promise when calling nft_transfer to the winner.
+        pays the seller and the royalty receiver if the token was transferred, refunds the winner otherwise
+    */
+    #[private]
+    pub fn resolve_settle_auction(&mut self, auction: Auction, winner_id: AccountId, price: U128) {
+        if !is_promise_success() {
+            log!("Could not transfer token {} to {}", auction.token_id, winner_id);
+            self.internal_refund(winner_id, price.0);
+            self.internal_return_auction_token(auction);
+            return;
+        }
+
+        //pay the seller and the royalty receiver
+        self.internal_payout(&auction.nft_contract_id, auction.owner_id, price.0);
+        //keep the owner of the pet up to date in the game
+        self.internal_sync_pet_owner(&auction.nft_contract_id, &auction.token_id);
+    }
+
+    //get the price a dutch auction can be won at right now, or the lowest next bid of an english auction
+    pub fn get_auction_price(&self, auction_id: U64) -> U128 {
+        let auction = self.auctions.get(&auction_id.0).expect("No auction");
+        U128(current_price(&auction, env::block_timestamp()))
+    }
+}
+
+impl Contract {
+    //start an auction for a token escrowed through nft_transfer_call
+    pub(crate) fn internal_create_auction(
+        &mut self,
+        owner_id: AccountId,
+        nft_contract_id: AccountId,
+        token_id: TokenId,
+        args: AuctionArgs,
+    ) -> AuctionId {
+        let start_at = args.start_at.map(|t| t.0).unwrap_or_else(env::block_timestamp);
+        assert!(args.end_at.0 > start_at, "Auction must end after it starts");
+        match args.kind {
+            AuctionKind::English => assert!(
+                args.reserve_price.0 >= args.start_price.0,
+                "Reserve price can't be lower than the start price"
+            ),
+            AuctionKind::Dutch => assert!(
+                args.start_price.0 > args.reserve_price.0,
+                "Start price must be greater than the reserve price"
+            ),
+        }
+        assert!(args.reserve_price.0 > 0, "Reserve price must be greater than 0");
+
+        self.next_auction_id += 1;
+        let auction_id = self.next_auction_id;
+        let auction = Auction {
+            auction_id: U64(auction_id),
+            kind: args.kind,
+            owner_id: owner_id.clone(),
+            nft_contract_id,
+            token_id,
+            start_price: args.start_price,
+            reserve_price: args.reserve_price,
+            min_bid_increment: args.min_bid_increment.unwrap_or(U128(0)),
+            start_at: U64(start_at),
+            end_at: args.end_at,
+            highest_bidder: None,
+            highest_bid: U128(0),
+        };
+        self.auctions.insert(&auction_id, &auction);
+
+        let count = self.auction_count_by_owner_id.get(&owner_id).unwrap_or(0);
+        self.auction_count_by_owner_id.insert(&owner_id, &(count + 1));
+
+        auction_id
+    }
+
+    //place a bid with the JOY transferred through ft_transfer_call, returns the JOY to refund to the bidder
+    pub(crate) fn internal_place_bid(
+        &mut self,
+        bidder_id: AccountId,
+        auction_id: AuctionId,
+        amount: U128,
+    ) -> U128 {
+        let mut auction = self.auctions.get(&auction_id).expect("No auction");
+        let now = env::block_timestamp();
+
+        assert_ne!(auction.owner_id, bidder_id, "Cannot bid on your own auction.");
+        assert!(now >= auction.start_at.0, "Auction has not started yet");
+        assert!(now < auction.end_at.0, "Auction has ended");
+
+        let price = current_price(&auction, now);
+        assert!(
+            amount.0 >= price,
+            "Bid must be greater than or equal to the current price: {}",
+            price
+        );
+
+        match auction.kind {
+            //the highest bid is held by the market until the auction is settled, the previous one is refunded
+            AuctionKind::English => {
+                if let Some(previous_bidder_id) = auction.highest_bidder.take() {
+                    self.internal_refund(previous_bidder_id, auction.highest_bid.0);
+                }
+                auction.highest_bidder = Some(bidder_id);
+                auction.highest_bid = amount;
+
+                //anti-sniping: a late bid gives the other bidders time to answer
+                if auction.end_at.0 - now < ANTI_SNIPING_WINDOW {
+                    auction.end_at = U64(now + ANTI_SNIPING_WINDOW);
+                }
+
+                self.auctions.insert(&auction_id, &auction);
+                U128(0)
+            }
+            //the first bid wins at the current price, anything above it is refunded
+            AuctionKind::Dutch => {
+                let auction = self.internal_remove_auction(auction_id);
+                self.internal_settle_auction(auction, bidder_id, price);
+                U128(amount.0 - price)
+            }
+        }
+    }
+
+    //transfer the escrowed token to the winner and pay out in the callback
+    fn internal_settle_auction(&mut self, auction: Auction, winner_id: AccountId, price: u128) {
+        ext_contract::ext(auction.nft_contract_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_NFT_TRANSFER)
+            .nft_transfer(
+                winner_id.clone(),
+                auction.token_id.clone(),
+                None,
+                Some("auction settlement".to_string()),
+            )
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_SETTLE_AUCTION)
+                    .resolve_settle_auction(auction, winner_id, U128(price)),
+            );
+    }
+
+    //give the escrowed token back to the owner of the auction and sync the pet owner in the game afterwards
+    fn internal_return_auction_token(&self, auction: Auction) {
+        let transfer = ext_contract::ext(auction.nft_contract_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_NFT_TRANSFER)
+            .nft_transfer(
+                auction.owner_id,
+                auction.token_id.clone(),
+                None,
+                Some("auction returned".to_string()),
+            );
+
+        if let Some(sync) = self.internal_sync_pet_owner(&auction.nft_contract_id, &auction.token_id) {
+            transfer.then(sync);
+        }
+    }
+
+    //internal method for removing an auction from the market. This returns the removed auction object
+    fn internal_remove_auction(&mut self, auction_id: AuctionId) -> Auction {
+        let auction = self.auctions.remove(&auction_id).expect("No auction");
+
+        let count = self
+            .auction_count_by_owner_id
+            .get(&auction.owner_id)
+            .unwrap_or(0);
+        if count <= 1 {
+            self.auction_count_by_owner_id.remove(&auction.owner_id);
+        } else {
+            self.auction_count_by_owner_id
+                .insert(&auction.owner_id, &(count - 1));
+        }
+
+        auction
+    }
+}
+
+//english: the lowest next bid, dutch: the price decreasing linearly from the start price to the reserve price
+fn current_price(auction: &Auction, now: u64) -> u128 {
+    match auction.kind {
+        AuctionKind::English => match auction.highest_bidder {
+            Some(_) => auction.highest_bid.0 + auction.min_bid_increment.0.max(1),
+            None => auction.start_price.0,
+        },
+        AuctionKind::Dutch => {
+            let start_at = auction.start_at.0;
+            let duration = (auction.end_at.0 - start_at) as u128;
+            let elapsed = (now.clamp(start_at, auction.end_at.0) - start_at) as u128;
+            let drop = auction.start_price.0 - auction.reserve_price.0;
+            auction.start_price.0 - drop * elapsed / duration
+        }
+    }
+}
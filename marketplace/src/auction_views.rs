@@ -0,0 +1,30 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //views
+
+    //returns the number of auctions the marketplace has up (as a string)
+    pub fn get_supply_auctions(&self) -> U64 {
+        U64(self.auctions.len())
+    }
+
+    //get the information of an auction
+    pub fn get_auction(&self, auction_id: U64) -> Option<Auction> {
+        self.auctions.get(&auction_id.0)
+    }
+
+    //returns paginated auction objects (result is a vector of auctions)
+    pub fn get_auctions(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Auction> {
+        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        self.auctions
+            .values()
+            //skip to the index we specified in the start variable
+            .skip(start as usize)
+            //take the first "limit" elements in the vector. If we didn't specify a limit, use 50
+            .take(limit.unwrap_or(50) as usize)
+            .collect()
+    }
+}
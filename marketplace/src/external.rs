@@ -0,0 +1,43 @@
+use crate::*;
+use near_sdk::ext_contract;
+
+//the lock placed on a token by a game contract, as returned by nft_lock_info on the NFT contracts
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenLock {
+    pub locked_by: AccountId,
+    pub reason: String,
+    pub locked_at: u64,
+}
+
+//external contract calls
+
+//initiate a cross contract call to the nft contract. This will transfer the token to the buyer
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+#[ext_contract(ext_contract)]
+trait ExtContract {
+    fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId, //purchaser (person to transfer the NFT to)
+        token_id: TokenId, //token ID to transfer
+        approval_id: Option<u64>, //market contract's approval ID in order to transfer the token on behalf of the owner, None for escrowed tokens
+        memo: Option<String>, //memo (to include some context)
+    );
+
+    //get why and by whom a token is locked, if it is locked
+    fn nft_lock_info(&self, token_id: TokenId) -> Option<TokenLock>;
+}
+
+//initiate a cross contract call to the JOY contract to pay out sellers and royalty receivers
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+#[ext_contract(ext_ft)]
+trait ExtFungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
+}
+
+//initiate a cross contract call to the game contract to update the owner of a traded pet
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+#[ext_contract(ext_game)]
+trait ExtGame {
+    fn sync_pet_owner(&mut self, pet_id: u64);
+}
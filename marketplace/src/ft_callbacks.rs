@@ -0,0 +1,44 @@
+use crate::*;
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+
+/// transfer callbacks from the JOY contract
+
+//the msg passed to ft_transfer_call by the buyer, it tells what the JOY is paying for
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde", untagged)]
+pub enum FtOnTransferArgs {
+    //buy a token listed for a fixed price
+    Purchase {
+        nft_contract_id: AccountId,
+        token_id: TokenId,
+    },
+    //bid on an auction
+    Bid { auction_id: U64 },
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for Contract {
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        //only JOY is accepted as payment
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.ft_contract_id,
+            "Only JOY is accepted"
+        );
+
+        match near_sdk::serde_json::from_str(&msg).expect("Not valid FtOnTransferArgs") {
+            FtOnTransferArgs::Purchase {
+                nft_contract_id,
+                token_id,
+            } => self.internal_purchase(sender_id, nft_contract_id, token_id, amount),
+            FtOnTransferArgs::Bid { auction_id } => {
+                PromiseOrValue::Value(self.internal_place_bid(sender_id, auction_id.0, amount))
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //set the game contract notified when a pet of its pet NFT contract is traded (only the contract owner)
+    pub fn set_game_contract(&mut self, game_contract_id: AccountId, pet_nft_contract_id: AccountId) {
+        self.assert_owner();
+        self.game_contract_id = Some(game_contract_id);
+        self.pet_nft_contract_id = Some(pet_nft_contract_id);
+    }
+
+    //get the game contract and the pet NFT contract it keeps owners for
+    pub fn get_game_contract(&self) -> Option<(AccountId, AccountId)> {
+        self.game_contract_id
+            .clone()
+            .zip(self.pet_nft_contract_id.clone())
+    }
+}
+
+impl Contract {
+    //tell the game that a pet changed owner so it can update its own record, returns the call so it can be chained
+    pub(crate) fn internal_sync_pet_owner(
+        &self,
+        nft_contract_id: &AccountId,
+        token_id: &TokenId,
+    ) -> Option<Promise> {
+        let (game_contract_id, pet_nft_contract_id) = self.get_game_contract()?;
+        if &pet_nft_contract_id != nft_contract_id {
+            return None;
+        }
+
+        //pet token IDs are the pet IDs of the game
+        let pet_id = token_id.parse::<u64>().ok()?;
+        Some(
+            ext_game::ext(game_contract_id)
+                .with_static_gas(GAS_FOR_SYNC_PET_OWNER)
+                .sync_pet_owner(pet_id),
+        )
+    }
+}
@@ -0,0 +1,106 @@
+use crate::*;
+
+//used to generate a unique prefix in our storage collections (this is to avoid data collisions)
+pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
+    //get the default hash
+    let mut hash = CryptoHash::default();
+    //we hash the account ID and return it
+    hash.copy_from_slice(&env::sha256(account_id.as_bytes()));
+    hash
+}
+
+//check that the last promise the callback waited on succeeded
+pub(crate) fn is_promise_success() -> bool {
+    assert_eq!(
+        env::promise_results_count(),
+        1,
+        "Contract expected a result on the callback"
+    );
+    matches!(env::promise_result(0), near_sdk::PromiseResult::Successful(_))
+}
+
+impl Contract {
+    //make sure that the caller is the owner of the contract
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only the contract owner can call this method"
+        );
+    }
+
+    //the number of sales and auctions an account is paying storage for
+    pub(crate) fn internal_storage_slots_used(&self, account_id: &AccountId) -> u64 {
+        let sales = self
+            .by_owner_id
+            .get(account_id)
+            .map(|s| s.len())
+            .unwrap_or_default();
+        let auctions = self.auction_count_by_owner_id.get(account_id).unwrap_or(0);
+        sales + auctions
+    }
+
+    //make sure that the account paid storage for 1 EXTRA sale or auction
+    pub(crate) fn assert_storage_for_new_slot(&self, account_id: &AccountId) {
+        //get the total storage paid by the owner
+        let owner_paid_storage = self.storage_deposits.get(account_id).unwrap_or(0);
+        //get the storage required which is simply the storage for the number of sales and auctions they have + 1
+        let storage_required =
+            (self.internal_storage_slots_used(account_id) + 1) as u128 * STORAGE_PER_SALE;
+
+        //make sure that the total paid is >= the required storage
+        assert!(
+            owner_paid_storage >= storage_required,
+            "Insufficient storage paid: {}, for {} sales at {} rate of per sale",
+            owner_paid_storage,
+            storage_required / STORAGE_PER_SALE,
+            STORAGE_PER_SALE
+        );
+    }
+
+    //internal method for removing a sale from the market. This returns the previously removed sale object
+    pub(crate) fn internal_remove_sale(
+        &mut self,
+        nft_contract_id: AccountId,
+        token_id: TokenId,
+    ) -> Sale {
+        //get the unique sale ID (contract + DELIMITER + token ID)
+        let contract_and_token_id = format!("{}{}{}", &nft_contract_id, DELIMETER, token_id);
+        //get the sale object by removing the unique sale ID. If there was no sale, panic
+        let sale = self.sales.remove(&contract_and_token_id).expect("No sale");
+
+        //get the set of sales for the sale's owner. If there's no sale, panic.
+        let mut by_owner_id = self.by_owner_id.get(&sale.owner_id).expect("No sale by_owner_id");
+        //remove the unique sale ID from the set of sales
+        by_owner_id.remove(&contract_and_token_id);
+
+        //if the set of sales is now empty after removing the unique sale ID, we simply remove that owner from the map
+        if by_owner_id.is_empty() {
+            self.by_owner_id.remove(&sale.owner_id);
+        //if the set of sales is not empty after removing, we insert the set back into the map for the owner
+        } else {
+            self.by_owner_id.insert(&sale.owner_id, &by_owner_id);
+        }
+
+        //get the set of token IDs for sale for the nft contract ID. If there's no sale, panic.
+        let mut by_nft_contract_id = self
+            .by_nft_contract_id
+            .get(&nft_contract_id)
+            .expect("No sale by nft_contract_id");
+
+        //remove the token ID from the set
+        by_nft_contract_id.remove(&token_id);
+
+        //if the set is now empty after removing the token ID, we remove that nft contract ID from the map
+        if by_nft_contract_id.is_empty() {
+            self.by_nft_contract_id.remove(&nft_contract_id);
+        //if the set is not empty after removing, we insert the set back into the map for the nft contract ID
+        } else {
+            self.by_nft_contract_id
+                .insert(&nft_contract_id, &by_nft_contract_id);
+        }
+
+        //return the sale object
+        sale
+    }
+}
@@ -0,0 +1,200 @@
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::collections::{LookupMap, UnorderedMap, UnorderedSet};
+use near_sdk::json_types::{U128, U64};
+use near_sdk::serde::{Deserialize, Serialize};
+use near_sdk::{
+    assert_one_yocto, env, near_bindgen, AccountId, Balance, BorshStorageKey, CryptoHash, Gas,
+    PanicOnDefault, Promise, PromiseOrValue,
+};
+
+use crate::auction::*;
+use crate::external::*;
+use crate::internal::*;
+use crate::royalty::*;
+use crate::sale::*;
+use near_sdk::env::STORAGE_PRICE_PER_BYTE;
+
+mod auction;
+mod auction_views;
+mod external;
+mod ft_callbacks;
+mod game;
+mod internal;
+mod nft_callbacks;
+mod royalty;
+mod sale;
+mod sale_views;
+
+//GAS constants to attach to calls
+const GAS_FOR_NFT_LOCK_INFO: Gas = Gas(5_000_000_000_000);
+const GAS_FOR_RESOLVE_LOCK_CHECK: Gas = Gas(100_000_000_000_000);
+const GAS_FOR_NFT_TRANSFER: Gas = Gas(15_000_000_000_000);
+const GAS_FOR_RESOLVE_PURCHASE: Gas = Gas(60_000_000_000_000);
+const GAS_FOR_RESOLVE_SETTLE_AUCTION: Gas = Gas(60_000_000_000_000);
+const GAS_FOR_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
+const GAS_FOR_SYNC_PET_OWNER: Gas = Gas(20_000_000_000_000);
+
+//1 yoctoNEAR is attached to nft_transfer and ft_transfer
+const ONE_YOCTO: Balance = 1;
+
+//the minimum storage to have a sale or an auction on the contract.
+const STORAGE_PER_SALE: u128 = 1000 * STORAGE_PRICE_PER_BYTE;
+
+//every sale will have a unique ID which is `CONTRACT + DELIMITER + TOKEN_ID`
+static DELIMETER: &str = ".";
+
+//Creating custom types to use within the contract. This makes things more readable.
+pub type SalePriceInJoy = U128;
+pub type TokenId = String;
+pub type ContractAndTokenId = String;
+
+//main contract struct to store all the information
+#[near_bindgen]
+#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+pub struct Contract {
+    //keep track of the owner of the contract
+    pub owner_id: AccountId,
+
+    //the JOY fungible token that sales are priced and paid in
+    pub ft_contract_id: AccountId,
+
+    /*
+        to keep track of the sales, we map the ContractAndTokenId to a Sale.
+        the ContractAndTokenId is the unique identifier for every sale. It is made
+        up of the `contract ID + DELIMITER + token ID`
+    */
+    pub sales: UnorderedMap<ContractAndTokenId, Sale>,
+
+    //keep track of all the Sale IDs for every account ID
+    pub by_owner_id: LookupMap<AccountId, UnorderedSet<ContractAndTokenId>>,
+
+    //keep track of all the token IDs for sale for a given contract
+    pub by_nft_contract_id: LookupMap<AccountId, UnorderedSet<TokenId>>,
+
+    //the NFT contracts that can be traded on the marketplace and the royalty paid on their sales
+    pub royalty_by_nft_contract_id: UnorderedMap<AccountId, Royalty>,
+
+    //keep track of the storage that accounts have payed
+    pub storage_deposits: LookupMap<AccountId, Balance>,
+
+    //the auctions of escrowed tokens by auction ID
+    pub auctions: UnorderedMap<AuctionId, Auction>,
+
+    //the ID of the last auction created
+    pub next_auction_id: AuctionId,
+
+    //keep track of the number of auctions for every account ID
+    pub auction_count_by_owner_id: LookupMap<AccountId, u64>,
+
+    //the game contract notified when a pet is traded and the pet NFT contract it keeps owners for
+    pub game_contract_id: Option<AccountId>,
+    pub pet_nft_contract_id: Option<AccountId>,
+}
+
+/// Helper structure for keys of the persistent collections.
+#[derive(BorshStorageKey, BorshSerialize)]
+pub enum StorageKey {
+    Sales,
+    ByOwnerId,
+    ByOwnerIdInner { account_id_hash: CryptoHash },
+    ByNFTContractId,
+    ByNFTContractIdInner { account_id_hash: CryptoHash },
+    RoyaltyByNFTContractId,
+    StorageDeposits,
+    Auctions,
+    AuctionCountByOwnerId,
+}
+
+#[near_bindgen]
+impl Contract {
+    /*
+        initialization function (can only be called once).
+        this initializes the contract with an owner and the JOY token used for payments.
+    */
+    #[init]
+    pub fn new(owner_id: AccountId, ft_contract_id: AccountId) -> Self {
+        Self {
+            owner_id,
+            ft_contract_id,
+            sales: UnorderedMap::new(StorageKey::Sales),
+            by_owner_id: LookupMap::new(StorageKey::ByOwnerId),
+            by_nft_contract_id: LookupMap::new(StorageKey::ByNFTContractId),
+            royalty_by_nft_contract_id: UnorderedMap::new(StorageKey::RoyaltyByNFTContractId),
+            storage_deposits: LookupMap::new(StorageKey::StorageDeposits),
+            auctions: UnorderedMap::new(StorageKey::Auctions),
+            next_auction_id: 0,
+            auction_count_by_owner_id: LookupMap::new(StorageKey::AuctionCountByOwnerId),
+            game_contract_id: None,
+            pet_nft_contract_id: None,
+        }
+    }
+
+    //Allows users to deposit storage. This is to cover the cost of storing sale objects on the contract
+    //Optional account ID is to users can pay for storage for other people.
+    #[payable]
+    pub fn storage_deposit(&mut self, account_id: Option<AccountId>) {
+        //get the account ID to pay for storage for
+        let storage_account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        //get the deposit value which is how much the user wants to add to their storage
+        let deposit = env::attached_deposit();
+
+        //make sure the deposit is greater than or equal to the minimum storage for a sale
+        assert!(
+            deposit >= STORAGE_PER_SALE,
+            "Requires minimum deposit of {}",
+            STORAGE_PER_SALE
+        );
+
+        //get the balance of the account (if the account isn't in the map we default to a balance of 0)
+        let mut balance: u128 = self.storage_deposits.get(&storage_account_id).unwrap_or(0);
+        //add the deposit to their balance
+        balance += deposit;
+        //insert the balance back into the map for that account ID
+        self.storage_deposits.insert(&storage_account_id, &balance);
+    }
+
+    //Allows users to withdraw any excess storage that they're not using. Say Bob pays 0.01N for 1 sale
+    //Alice then buys Bob's token. This means bob has paid 0.01N for a sale that's no longer on the marketplace
+    //Bob could then withdraw this 0.01N back into his account.
+    #[payable]
+    pub fn storage_withdraw(&mut self) {
+        //make sure the user attaches exactly 1 yoctoNEAR for security purposes.
+        //this will redirect them to the NEAR wallet (or requires a full access key).
+        assert_one_yocto();
+
+        //the account to withdraw storage to is always the function caller
+        let owner_id = env::predecessor_account_id();
+        //get the amount that the user has by removing them from the map. If they're not in the map, default to 0
+        let mut amount = self.storage_deposits.remove(&owner_id).unwrap_or(0);
+
+        //how many sales and auctions is that user taking up currently
+        let len = self.internal_storage_slots_used(&owner_id);
+        //how much NEAR is being used up for all the current sales and auctions on the account
+        let diff = u128::from(len) * STORAGE_PER_SALE;
+
+        //the excess to withdraw is the total storage paid - storage being used up.
+        amount -= diff;
+
+        //if that excess to withdraw is > 0, we transfer the amount to the user.
+        if amount > 0 {
+            Promise::new(owner_id.clone()).transfer(amount);
+        }
+        //we need to add back the storage being used up into the map if it's greater than 0.
+        //this is so that if the user had 500 sales on the market, we insert that value here so
+        //if those sales get taken down, the user can then go and withdraw 500 sales worth of storage.
+        if diff > 0 {
+            self.storage_deposits.insert(&owner_id, &diff);
+        }
+    }
+
+    /// views
+    //return the minimum storage for 1 sale
+    pub fn storage_minimum_balance(&self) -> U128 {
+        U128(STORAGE_PER_SALE)
+    }
+
+    //return how much storage an account has paid for
+    pub fn storage_balance_of(&self, account_id: AccountId) -> U128 {
+        U128(self.storage_deposits.get(&account_id).unwrap_or(0))
+    }
+}
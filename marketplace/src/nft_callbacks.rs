@@ -0,0 +1,169 @@
+use crate::*;
+
+/// approval callbacks from NFT Contracts
+
+//struct for keeping track of the sale conditions for a Sale
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct SaleArgs {
+    pub sale_conditions: SalePriceInJoy,
+}
+
+/*
+    trait that will be used as the callback from the NFT contract. When nft_approve is
+    called, it will fire a cross contract call to this marketplace and this is the function
+    that is invoked.
+*/
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+trait NonFungibleTokenApprovalsReceiver {
+    fn nft_on_approve(
+        &mut self,
+        token_id: TokenId,
+        owner_id: AccountId,
+        approval_id: u64,
+        msg: String,
+    );
+}
+
+/*
+    trait that will be used as the callback from the NFT contract. When nft_transfer_call is
+    called with the marketplace as receiver, the token is escrowed for an auction.
+*/
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+trait NonFungibleTokenReceiver {
+    fn nft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: TokenId,
+        msg: String,
+    ) -> PromiseOrValue<bool>;
+}
+
+//implementation of the trait
+#[near_bindgen]
+impl NonFungibleTokenApprovalsReceiver for Contract {
+    /// where we add the sale because we know nft owner can only call nft_approve
+    fn nft_on_approve(
+        &mut self,
+        token_id: TokenId,
+        owner_id: AccountId,
+        approval_id: u64,
+        msg: String,
+    ) {
+        // get the contract ID which is the predecessor
+        let nft_contract_id = env::predecessor_account_id();
+        //get the signer which is the person who initiated the transaction
+        let signer_id = env::signer_account_id();
+
+        //make sure that the signer isn't the predecessor. This is so that we're sure
+        //this was called via a cross-contract call
+        assert_ne!(
+            nft_contract_id,
+            signer_id,
+            "nft_on_approve should only be called via cross-contract call"
+        );
+        //make sure the owner ID is the signer.
+        assert_eq!(owner_id, signer_id, "owner_id should be signer_id");
+
+        //only the pets and items of the game can be traded here
+        assert!(
+            self.royalty_by_nft_contract_id.get(&nft_contract_id).is_some(),
+            "NFT contract is not supported by the marketplace"
+        );
+
+        //we need to enforce that the user has enough storage for 1 EXTRA sale.
+        self.assert_storage_for_new_slot(&signer_id);
+
+        //if all these checks pass we can create the sale conditions object.
+        let SaleArgs { sale_conditions } =
+            //the sale conditions come from the msg field. The market assumes that the user passed
+            //in a proper msg. If they didn't, it panics.
+            near_sdk::serde_json::from_str(&msg).expect("Not valid SaleArgs");
+        assert!(sale_conditions.0 > 0, "Price must be greater than 0");
+
+        //create the unique sale ID which is the contract + DELIMITER + token ID
+        let contract_and_token_id = format!("{}{}{}", nft_contract_id, DELIMETER, token_id);
+
+        //insert the key value pair into the sales map. Key is the unique ID. value is the sale object
+        self.sales.insert(
+            &contract_and_token_id,
+            &Sale {
+                owner_id: owner_id.clone(), //owner of the sale / token
+                approval_id, //approval ID for that token that was given to the market
+                nft_contract_id: nft_contract_id.to_string(), //NFT contract the token was minted on
+                token_id: token_id.clone(), //the actual token ID
+                sale_conditions, //the sale conditions
+            },
+        );
+
+        //Extra functionality that populates collections necessary for the view calls
+
+        //get the sales by owner ID for the given owner. If there are none, we create a new empty set
+        let mut by_owner_id = self.by_owner_id.get(&owner_id).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::ByOwnerIdInner {
+                //we get a new unique prefix for the collection by hashing the owner
+                account_id_hash: hash_account_id(&owner_id),
+            })
+        });
+
+        //insert the unique sale ID into the set
+        by_owner_id.insert(&contract_and_token_id);
+        //insert that set back into the collection for the owner
+        self.by_owner_id.insert(&owner_id, &by_owner_id);
+
+        //get the token IDs for the given nft contract ID. If there are none, we create a new empty set
+        let mut by_nft_contract_id = self
+            .by_nft_contract_id
+            .get(&nft_contract_id)
+            .unwrap_or_else(|| {
+                UnorderedSet::new(StorageKey::ByNFTContractIdInner {
+                    //we get a new unique prefix for the collection by hashing the owner
+                    account_id_hash: hash_account_id(&nft_contract_id),
+                })
+            });
+
+        //insert the token ID into the set
+        by_nft_contract_id.insert(&token_id);
+        //insert the set back into the collection for the given nft contract ID
+        self.by_nft_contract_id
+            .insert(&nft_contract_id, &by_nft_contract_id);
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenReceiver for Contract {
+    /// where we start the auction because the token is now held by the marketplace
+    fn nft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: TokenId,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        // get the contract ID which is the predecessor
+        let nft_contract_id = env::predecessor_account_id();
+
+        //only the pets and items of the game can be auctioned here
+        assert!(
+            self.royalty_by_nft_contract_id.get(&nft_contract_id).is_some(),
+            "NFT contract is not supported by the marketplace"
+        );
+        //only the owner of the token can start an auction
+        assert_eq!(
+            sender_id, previous_owner_id,
+            "Only the token owner can start an auction"
+        );
+
+        //we need to enforce that the user has enough storage for 1 EXTRA auction.
+        self.assert_storage_for_new_slot(&previous_owner_id);
+
+        //if any check fails, the NFT contract gives the token back to the owner
+        let args: AuctionArgs =
+            near_sdk::serde_json::from_str(&msg).expect("Not valid AuctionArgs");
+        self.internal_create_auction(previous_owner_id, nft_contract_id, token_id, args);
+
+        //keep the token in escrow
+        PromiseOrValue::Value(false)
+    }
+}
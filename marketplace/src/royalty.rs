@@ -0,0 +1,85 @@
+use crate::*;
+
+//the highest royalty that can be configured for an NFT contract (25%)
+const MAX_ROYALTY_BASIS_POINTS: u32 = 2_500;
+
+//the royalty paid on every sale of an NFT contract, in basis points of the sale price
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Royalty {
+    //the account receiving the royalty
+    pub receiver_id: AccountId,
+    //the share of the sale price paid to the receiver, 100 = 1%
+    pub basis_points: u32,
+}
+
+#[near_bindgen]
+impl Contract {
+    //allow the tokens of an NFT contract to be traded and set the royalty paid on its sales (only the contract owner)
+    pub fn set_nft_contract_royalty(
+        &mut self,
+        nft_contract_id: AccountId,
+        receiver_id: AccountId,
+        basis_points: u32,
+    ) {
+        self.assert_owner();
+        assert!(
+            basis_points <= MAX_ROYALTY_BASIS_POINTS,
+            "Royalty can't be more than {} basis points",
+            MAX_ROYALTY_BASIS_POINTS
+        );
+
+        self.royalty_by_nft_contract_id.insert(
+            &nft_contract_id,
+            &Royalty {
+                receiver_id,
+                basis_points,
+            },
+        );
+    }
+
+    //stop accepting new listings from an NFT contract (only the contract owner)
+    pub fn remove_nft_contract(&mut self, nft_contract_id: AccountId) {
+        self.assert_owner();
+        self.royalty_by_nft_contract_id.remove(&nft_contract_id);
+    }
+
+    //get the royalty of an NFT contract, None if its tokens can't be traded on the marketplace
+    pub fn get_nft_contract_royalty(&self, nft_contract_id: AccountId) -> Option<Royalty> {
+        self.royalty_by_nft_contract_id.get(&nft_contract_id)
+    }
+
+    //get all the NFT contracts that can be traded on the marketplace along with their royalty
+    pub fn get_supported_nft_contracts(&self) -> Vec<(AccountId, Royalty)> {
+        self.royalty_by_nft_contract_id.to_vec()
+    }
+}
+
+impl Contract {
+    //pay the royalty of the nft contract, if any, and the rest of the price to the seller
+    pub(crate) fn internal_payout(&self, nft_contract_id: &AccountId, seller_id: AccountId, price: u128) {
+        let mut royalty = 0;
+        if let Some(nft_royalty) = self.royalty_by_nft_contract_id.get(nft_contract_id) {
+            royalty = price * nft_royalty.basis_points as u128 / 10_000;
+            if royalty > 0 {
+                ext_ft::ext(self.ft_contract_id.clone())
+                    .with_attached_deposit(ONE_YOCTO)
+                    .with_static_gas(GAS_FOR_FT_TRANSFER)
+                    .ft_transfer(nft_royalty.receiver_id, U128(royalty), None);
+            }
+        }
+
+        ext_ft::ext(self.ft_contract_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_FT_TRANSFER)
+            .ft_transfer(seller_id, U128(price - royalty), None);
+    }
+
+    //send JOY back to a buyer or bidder
+    pub(crate) fn internal_refund(&self, account_id: AccountId, amount: u128) {
+        ext_ft::ext(self.ft_contract_id.clone())
+            .with_attached_deposit(ONE_YOCTO)
+            .with_static_gas(GAS_FOR_FT_TRANSFER)
+            .ft_transfer(account_id, U128(amount), None);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::*;
+use near_sdk::{log, PromiseError};
+
+//struct that holds important information about each sale on the market
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct Sale {
+    //owner of the sale
+    pub owner_id: AccountId,
+    //market contract's approval ID to transfer the token on behalf of the owner
+    pub approval_id: u64,
+    //nft contract where the token was minted
+    pub nft_contract_id: String,
+    //actual token ID for sale
+    pub token_id: String,
+    //sale price in JOY that the token is listed for
+    pub sale_conditions: SalePriceInJoy,
+}
+
+#[near_bindgen]
+impl Contract {
+    //removes a sale from the market.
+    #[payable]
+    pub fn remove_sale(&mut self, nft_contract_id: AccountId, token_id: String) {
+        //assert that the user has attached exactly 1 yoctoNEAR (for security reasons)
+        assert_one_yocto();
+        //get the sale object as the return value from removing the sale internally
+        let sale = self.internal_remove_sale(nft_contract_id, token_id);
+        //get the predecessor of the call and make sure they're the owner of the sale
+        let owner_id = env::predecessor_account_id();
+        //if this fails, the remove sale will revert
+        assert_eq!(owner_id, sale.owner_id, "Must be sale owner");
+    }
+
+    //updates the price for a sale on the market
+    #[payable]
+    pub fn update_price(&mut self, nft_contract_id: AccountId, token_id: String, price: U128) {
+        //assert that the user has attached exactly 1 yoctoNEAR (for security reasons)
+        assert_one_yocto();
+        assert!(price.0 > 0, "Price must be greater than 0");
+
+        //create the unique sale ID from the nft contract and token
+        let contract_id: AccountId = nft_contract_id;
+        let contract_and_token_id = format!("{}{}{}", contract_id, DELIMETER, token_id);
+
+        //get the sale object from the unique sale ID. If there is no token, panic.
+        let mut sale = self.sales.get(&contract_and_token_id).expect("No sale");
+
+        //assert that the caller of the function is the sale owner
+        assert_eq!(
+            env::predecessor_account_id(),
+            sale.owner_id,
+            "Must be sale owner"
+        );
+
+        //set the sale conditions equal to the passed in price
+        sale.sale_conditions = price;
+        //insert the sale back into the map for the unique sale ID
+        self.sales.insert(&contract_and_token_id, &sale);
+    }
+
+    /*
+        private method used to check that the token isn't locked by a game before settling the purchase.
+        the whole amount goes back to the buyer if the token is locked
+    */
+    #[private]
+    pub fn resolve_lock_check(
+        &mut self,
+        buyer_id: AccountId,
+        sale: Sale,
+        amount: U128,
+        #[callback_result] token_lock: Result<Option<TokenLock>, PromiseError>,
+    ) -> PromiseOrValue<U128> {
+        match token_lock {
+            Err(_) => {
+                log!("Could not check the lock of token {}", sale.token_id);
+                PromiseOrValue::Value(amount)
+            }
+            //staked pets and mining tools in use can't be bought
+            Ok(Some(token_lock)) => {
+                log!(
+                    "Token {} is locked by {} ({})",
+                    sale.token_id,
+                    token_lock.locked_by,
+                    token_lock.reason
+                );
+                PromiseOrValue::Value(amount)
+            }
+            Ok(None) => {
+                let nft_contract_id: AccountId = sale.nft_contract_id.parse().unwrap();
+
+                //initiate a cross contract call to the nft contract. This will transfer the token to the buyer
+                ext_contract::ext(nft_contract_id)
+                    // Attach 1 yoctoNEAR with static GAS equal to the GAS for nft transfer
+                    .with_attached_deposit(ONE_YOCTO)
+                    .with_static_gas(GAS_FOR_NFT_TRANSFER)
+                    .nft_transfer(
+                        buyer_id.clone(), //purchaser (person to transfer the NFT to)
+                        sale.token_id.clone(), //token ID to transfer
+                        Some(sale.approval_id), //market contract's approval ID in order to transfer the token on behalf of the owner
+                        Some("payout from market".to_string()), //memo (to include some context)
+                    )
+                    //after the transfer payout has been initiated, we resolve the promise by calling our own resolve_purchase function.
+                    .then(
+                        Self::ext(env::current_account_id())
+                            .with_static_gas(GAS_FOR_RESOLVE_PURCHASE)
+                            .resolve_purchase(buyer_id, sale, amount),
+                    )
+                    .into()
+            }
+        }
+    }
+
+    /*
+        private method used to resolve the promise when calling nft_transfer.
+        pays the seller and the royalty receiver if the token was transferred, returns the unused JOY to the buyer
+    */
+    #[private]
+    pub fn resolve_purchase(&mut self, buyer_id: AccountId, sale: Sale, amount: U128) -> U128 {
+        //if the token couldn't be transferred, the whole amount goes back to the buyer
+        if !is_promise_success() {
+            log!("Could not transfer token {} to {}", sale.token_id, buyer_id);
+            return amount;
+        }
+
+        let price = sale.sale_conditions.0;
+        let nft_contract_id: AccountId = sale.nft_contract_id.parse().unwrap();
+
+        //pay the seller and the royalty receiver
+        self.internal_payout(&nft_contract_id, sale.owner_id, price);
+        //keep the owner of the pet up to date in the game
+        self.internal_sync_pet_owner(&nft_contract_id, &sale.token_id);
+
+        //anything paid above the price is returned to the buyer
+        U128(amount.0 - price)
+    }
+}
+
+impl Contract {
+    //purchase a token listed for sale with the JOY transferred through ft_transfer_call
+    pub(crate) fn internal_purchase(
+        &mut self,
+        buyer_id: AccountId,
+        nft_contract_id: AccountId,
+        token_id: TokenId,
+        amount: U128,
+    ) -> PromiseOrValue<U128> {
+        //get the sale object from the unique sale ID. If the sale doesn't exist, panic.
+        let contract_and_token_id = format!("{}{}{}", nft_contract_id, DELIMETER, token_id);
+        let sale = self.sales.get(&contract_and_token_id).expect("No sale");
+
+        //make sure the buyer isn't the owner of the sale
+        assert_ne!(sale.owner_id, buyer_id, "Cannot bid on your own sale.");
+
+        //make sure the transferred amount is greater than or equal to the price
+        assert!(
+            amount.0 >= sale.sale_conditions.0,
+            "Attached amount must be greater than or equal to the current price: {:?}",
+            sale.sale_conditions.0
+        );
+
+        //the sale is removed first so the token can't be bought twice
+        let sale = self.internal_remove_sale(nft_contract_id.clone(), token_id.clone());
+
+        //make sure the token isn't locked by a game before transferring it
+        ext_contract::ext(nft_contract_id)
+            .with_static_gas(GAS_FOR_NFT_LOCK_INFO)
+            .nft_lock_info(token_id)
+            .then(
+                Self::ext(env::current_account_id())
+                    .with_static_gas(GAS_FOR_RESOLVE_LOCK_CHECK)
+                    .resolve_lock_check(buyer_id, sale, amount),
+            )
+            .into()
+    }
+}
@@ -46,6 +46,12 @@ impl NonFungibleTokenCore for Contract {
         //get the token object from the token ID
         let mut token = self.tokens_by_id.get(&token_id).expect("No token");
 
+        //locked tokens can't be approved until the game releases them
+        self.assert_not_locked(&token_id);
+
+        //tokens of a type that is locked against transfers can't be approved either
+        self.assert_token_type_not_locked(&token.token_type);
+
         //make sure that the person calling the function is the owner of the token
         assert_eq!(
             &env::predecessor_account_id(),
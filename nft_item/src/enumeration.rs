@@ -72,4 +72,77 @@ impl Contract {
             //since we turned the keys into an iterator, we need to turn it back into a vector to return
             .collect()
     }
-}
\ No newline at end of file
+
+    //get the total supply of NFTs of a given token type
+    pub fn nft_supply_for_type(&self, token_type: TokenType) -> U128 {
+        //get the set of tokens for the passed in type
+        let tokens_for_type_set = self.tokens_per_type.get(&token_type);
+
+        //if there is some set of tokens, we'll return the length as a U128
+        if let Some(tokens_for_type_set) = tokens_for_type_set {
+            U128(tokens_for_type_set.len() as u128)
+        } else {
+            //if there isn't a set of tokens for the passed in type, we'll return 0
+            U128(0)
+        }
+    }
+
+    //Query for all the tokens of a given token type
+    pub fn nft_tokens_for_type(
+        &self,
+        token_type: TokenType,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonToken> {
+        //get the set of tokens for the passed in type
+        let tokens = if let Some(tokens_for_type_set) = self.tokens_per_type.get(&token_type) {
+            tokens_for_type_set
+        } else {
+            //if there is no set of tokens, we'll simply return an empty vector.
+            return vec![];
+        };
+
+        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        tokens
+            .iter()
+            .skip(start as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
+            .collect()
+    }
+
+    //Query for the tokens of a given token type held by an owner
+    pub fn nft_tokens_for_owner_by_type(
+        &self,
+        account_id: AccountId,
+        token_type: TokenType,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<JsonToken> {
+        //get the set of tokens for the passed in owner
+        let tokens = if let Some(tokens_for_owner_set) = self.tokens_per_owner.get(&account_id) {
+            tokens_for_owner_set
+        } else {
+            //if there is no set of tokens, we'll simply return an empty vector.
+            return vec![];
+        };
+
+        //where to start pagination - if we have a from_index, we'll use that - otherwise start from 0 index
+        let start = u128::from(from_index.unwrap_or(U128(0)));
+
+        tokens
+            .iter()
+            //only keep the tokens of the passed in type
+            .filter(|token_id| {
+                self.tokens_by_id
+                    .get(token_id)
+                    .is_some_and(|token| token.token_type == token_type)
+            })
+            .skip(start as usize)
+            .take(limit.unwrap_or(50) as usize)
+            .map(|token_id| self.nft_token(token_id.clone()).unwrap())
+            .collect()
+    }
+}
@@ -41,6 +41,15 @@ pub(crate) fn hash_account_id(account_id: &AccountId) -> CryptoHash {
     hash
 }
 
+//used to generate a unique prefix for the tokens of a given type
+pub(crate) fn hash_token_type(token_type: &TokenType) -> CryptoHash {
+    //get the default hash
+    let mut hash = CryptoHash::default();
+    //we hash the borsh serialized token type and return it
+    hash.copy_from_slice(&env::sha256(&token_type.try_to_vec().unwrap()));
+    hash
+}
+
 //used to make sure the user attached exactly 1 yoctoNEAR
 pub(crate) fn assert_one_yocto() {
     assert_eq!(
@@ -133,6 +142,47 @@ impl Contract {
         }
     }
 
+    //add a token to the set of tokens of a given type
+    pub(crate) fn internal_add_token_to_type(&mut self, token_type: &TokenType, token_id: &TokenId) {
+        //get the set of tokens for the given type
+        let mut tokens_set = self.tokens_per_type.get(token_type).unwrap_or_else(|| {
+            //if there are no tokens of this type yet, we create a new unordered set
+            UnorderedSet::new(
+                StorageKey::TokensPerTypeInner {
+                    //we get a new unique prefix for the collection
+                    token_type_hash: hash_token_type(token_type),
+                }
+                .try_to_vec()
+                .unwrap(),
+            )
+        });
+
+        //we insert the token ID into the set
+        tokens_set.insert(token_id);
+
+        //we insert that set for the given token type.
+        self.tokens_per_type.insert(token_type, &tokens_set);
+    }
+
+    //remove a token from the set of tokens of a given type
+    pub(crate) fn internal_remove_token_from_type(&mut self, token_type: &TokenType, token_id: &TokenId) {
+        //we get the set of tokens of the given type
+        let mut tokens_set = self
+            .tokens_per_type
+            .get(token_type)
+            .expect("Token should be registered for its type");
+
+        //we remove the the token_id from the set of tokens
+        tokens_set.remove(token_id);
+
+        //if the token set is now empty, we remove the type from the tokens_per_type collection
+        if tokens_set.is_empty() {
+            self.tokens_per_type.remove(token_type);
+        } else {
+            self.tokens_per_type.insert(token_type, &tokens_set);
+        }
+    }
+
     //transfers the NFT to the receiver_id (internal method and can't be called directly via CLI).
     pub(crate) fn internal_transfer(
         &mut self,
@@ -146,6 +196,9 @@ impl Contract {
         //get the token object by passing in the token_id
         let token = self.tokens_by_id.get(token_id).expect("No token");
 
+        //locked tokens can't be transferred until the game releases them
+        self.assert_not_locked(token_id);
+
         //if the sender doesn't equal the owner, we check if the sender is in the approval list
         if sender_id != &token.owner_id {
             //if the token's approved account IDs doesn't contain the sender, we panic
@@ -171,6 +224,9 @@ impl Contract {
             }
         }
 
+        //we make sure that the token type isn't locked against transfers
+        self.assert_token_type_not_locked(&token.token_type);
+
         //we make sure that the sender isn't sending the token to themselves
         assert_ne!(
             &token.owner_id, receiver_id,
@@ -188,16 +244,44 @@ impl Contract {
             //reset the approval account IDs
             approved_account_ids: Default::default(),
             next_approval_id: token.next_approval_id,
+            token_type: token.token_type.clone(),
         };
         //insert that new token into the tokens_by_id, replacing the old entry
         self.tokens_by_id.insert(token_id, &new_token);
 
-        //if there was some memo attached, we log it.
-        if let Some(memo) = memo {
-            env::log_str(&format!("Memo: {}", memo).to_string());
+        // Default the authorized ID to be None for the logs.
+        let mut authorized_id = None;
+        //if the sender isn't the owner, they were approved to transfer the token
+        if sender_id != &token.owner_id {
+            authorized_id = Some(sender_id.to_string());
         }
 
+        // Construct the transfer log as per the events standard.
+        EventLog::nep171(EventLogVariant::NftTransfer(vec![NftTransferLog {
+            // The optional authorized account ID to transfer the token on behalf of the old owner.
+            authorized_id,
+            // The old owner's account ID.
+            old_owner_id: token.owner_id.to_string(),
+            // The account ID of the new owner of the token.
+            new_owner_id: receiver_id.to_string(),
+            // A vector containing the token IDs as strings.
+            token_ids: vec![token_id.to_string()],
+            // An optional memo to include.
+            memo,
+        }]))
+        .emit();
+
         //return the previous token object that was transferred.
         token
     }
-}
\ No newline at end of file
+
+    //log a metadata update event for the given token, authorized by the caller
+    pub(crate) fn internal_emit_metadata_update(&self, token_id: TokenId) {
+        EventLog::nep171(EventLogVariant::NftMetadataUpdate(vec![NftMetadataUpdateLog {
+            authorized_id: env::predecessor_account_id().to_string(),
+            token_ids: vec![token_id],
+            memo: None,
+        }]))
+        .emit();
+    }
+}
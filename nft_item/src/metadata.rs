@@ -60,6 +60,16 @@ pub enum ItemType {
     MineTool,
 }
 
+//The kind of game item a token represents, used for enumeration and type-level transfer locks
+#[derive(BorshDeserialize, BorshSerialize, Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub enum TokenType {
+    Tool,
+    Consumable,
+    Cosmetic,
+    EvolutionItem,
+}
+
 #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 pub struct JoychiTokenMetadata {
@@ -77,6 +87,20 @@ pub struct Token {
     pub approved_account_ids: HashMap<AccountId, u64>,
     //the next approval ID to give out.
     pub next_approval_id: u64,
+    //the kind of game item the token represents
+    pub token_type: TokenType,
+}
+
+//A lock placed on a token by a game contract while the token is staked, equipped or in use
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenLock {
+    //the game contract that locked the token
+    pub locked_by: AccountId,
+    //why the token is locked, e.g. "staking" or "mining"
+    pub reason: String,
+    //when the token was locked, Unix epoch in nanoseconds
+    pub locked_at: u64,
 }
 
 //The Json token is what will be returned from view calls.
@@ -91,6 +115,8 @@ pub struct JsonToken {
     pub metadata: TokenMetadata,
     //list of approved account IDs that have access to transfer the token. This maps an account ID to an approval ID
     pub approved_account_ids: HashMap<AccountId, u64>,
+    //the kind of game item the token represents
+    pub token_type: TokenType,
 }
 
 pub trait NonFungibleTokenMetadata {
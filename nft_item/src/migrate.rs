@@ -0,0 +1,80 @@
+use crate::*;
+
+//the token struct before token types were added
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldToken {
+    pub owner_id: AccountId,
+    pub approved_account_ids: HashMap<AccountId, u64>,
+    pub next_approval_id: u64,
+}
+
+//the contract state before token types, game minters and token locks were added
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldContract {
+    pub owner_id: AccountId,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub tokens_by_id: LookupMap<TokenId, OldToken>,
+    pub token_metadata_by_id: UnorderedMap<TokenId, TokenMetadata>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /*
+        migrate the state of a contract deployed before token types, game minters and token locks.
+        token_types gives the type of every existing token (e.g. Tool for the joy_v1 mining tools).
+        the game contract (joy_v1) passed in is allowed to mint, others are added with add_authorized_minter.
+    */
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate(token_types: Vec<(TokenId, TokenType)>, game_id: Option<AccountId>) -> Self {
+        let old_state: OldContract = env::state_read().expect("No state to migrate");
+        let token_types: HashMap<TokenId, TokenType> = token_types.into_iter().collect();
+
+        let mut this = Self {
+            owner_id: old_state.owner_id,
+            tokens_per_owner: old_state.tokens_per_owner,
+            //the tokens are rewritten below under the same storage prefix
+            tokens_by_id: LookupMap::new(StorageKey::TokensById.try_to_vec().unwrap()),
+            token_metadata_by_id: old_state.token_metadata_by_id,
+            metadata: old_state.metadata,
+            tokens_per_type: LookupMap::new(StorageKey::TokensPerType.try_to_vec().unwrap()),
+            token_types_locked: UnorderedSet::new(
+                StorageKey::TokenTypesLocked.try_to_vec().unwrap(),
+            ),
+            authorized_minters: UnorderedSet::new(
+                StorageKey::AuthorizedMinters.try_to_vec().unwrap(),
+            ),
+            token_locks: LookupMap::new(StorageKey::TokenLocks.try_to_vec().unwrap()),
+        };
+
+        //every token has metadata, so we walk the metadata keys to find the tokens to rewrite
+        let token_ids = this.token_metadata_by_id.keys_as_vector().to_vec();
+        for token_id in token_ids {
+            let old_token = match old_state.tokens_by_id.get(&token_id) {
+                Some(old_token) => old_token,
+                None => continue,
+            };
+
+            let token_type = token_types
+                .get(&token_id)
+                .cloned()
+                .unwrap_or_else(|| env::panic_str(&format!("No token type for {}", token_id)));
+
+            let token = Token {
+                owner_id: old_token.owner_id,
+                approved_account_ids: old_token.approved_account_ids,
+                next_approval_id: old_token.next_approval_id,
+                token_type,
+            };
+            this.tokens_by_id.insert(&token_id, &token);
+            this.internal_add_token_to_type(&token.token_type, &token_id);
+        }
+
+        if let Some(game_id) = game_id {
+            this.authorized_minters.insert(&game_id);
+        }
+
+        this
+    }
+}
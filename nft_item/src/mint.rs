@@ -3,7 +3,16 @@ use crate::*;
 #[near_bindgen]
 impl Contract {
     #[payable]
-    pub fn nft_mint(&mut self, token_id: TokenId, metadata: TokenMetadata, receiver_id: AccountId) {
+    pub fn nft_mint(
+        &mut self,
+        token_id: TokenId,
+        metadata: TokenMetadata,
+        receiver_id: AccountId,
+        token_type: Option<TokenType>,
+    ) {
+        //only the game contracts can mint new tokens
+        self.assert_authorized_minter();
+
         //measure the initial storage being used on the contract
         let initial_storage_usage = env::storage_usage();
 
@@ -15,6 +24,8 @@ impl Contract {
             approved_account_ids: Default::default(),
             //the next approval ID is set to 0
             next_approval_id: 0,
+            //tokens minted without a type are consumables
+            token_type: token_type.unwrap_or(TokenType::Consumable),
         };
 
         //insert the token ID and token struct and make sure that the token doesn't exist
@@ -29,6 +40,20 @@ impl Contract {
         //call the internal method for adding the token to the owner
         self.internal_add_token_to_owner(&token.owner_id, &token_id);
 
+        //call the internal method for adding the token to its type
+        self.internal_add_token_to_type(&token.token_type, &token_id);
+
+        // Construct the mint log as per the events standard.
+        EventLog::nep171(EventLogVariant::NftMint(vec![NftMintLog {
+            // Owner of the token.
+            owner_id: token.owner_id.to_string(),
+            // Vector of token IDs that were minted.
+            token_ids: vec![token_id.to_string()],
+            // An optional memo to include.
+            memo: None,
+        }]))
+        .emit();
+
         //calculate the required storage which was the used - initial
         let required_storage_in_bytes = env::storage_usage() - initial_storage_usage;
 
@@ -37,6 +62,8 @@ impl Contract {
     }
 
     pub fn update_medatada_item(&mut self, token_id: String, item_attribute: ItemAttribute) {
+        self.assert_authorized_minter();
+
         let mut token = self.token_metadata_by_id.get(&token_id).unwrap();
 
         let data: String = format!(
@@ -56,9 +83,13 @@ impl Contract {
         token.media = Some(item_attribute.item_image);
 
         self.token_metadata_by_id.insert(&token_id, &token);
+
+        self.internal_emit_metadata_update(token_id);
     }
 
     pub fn update_token_metadata(&mut self, token_id: String, token_metadata: TokenMetadata) {
+        self.assert_authorized_minter();
+
         let mut token = self.token_metadata_by_id.get(&token_id).unwrap();
 
         // Update new token metadata
@@ -76,5 +107,38 @@ impl Contract {
         token.reference_hash = token_metadata.reference_hash;
 
         self.token_metadata_by_id.insert(&token_id, &token);
+
+        self.internal_emit_metadata_update(token_id);
+    }
+
+    #[payable]
+    pub fn nft_burn(&mut self, token_id: TokenId, memo: Option<String>) {
+        //assert that the user attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+
+        //only the game contracts can burn tokens
+        self.assert_authorized_minter();
+
+        //get the token object
+        let token = self.tokens_by_id.get(&token_id).expect("No token");
+
+        //remove the token and its metadata from the contract
+        self.tokens_by_id.remove(&token_id);
+        self.token_metadata_by_id.remove(&token_id);
+        self.token_locks.remove(&token_id);
+        self.internal_remove_token_from_owner(&token.owner_id, &token_id);
+        self.internal_remove_token_from_type(&token.token_type, &token_id);
+
+        //we refund the owner for releasing the storage used up by the approved account IDs
+        refund_approved_account_ids(token.owner_id.clone(), &token.approved_account_ids);
+
+        // Construct the burn log as per the events standard.
+        EventLog::nep171(EventLogVariant::NftBurn(vec![NftBurnLog {
+            owner_id: token.owner_id.to_string(),
+            authorized_id: Some(env::predecessor_account_id().to_string()),
+            token_ids: vec![token_id],
+            memo,
+        }]))
+        .emit();
     }
 }
\ No newline at end of file
@@ -0,0 +1,46 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //allow a game contract to mint tokens and mutate token metadata (only the contract owner)
+    pub fn add_authorized_minter(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.authorized_minters.insert(&account_id);
+    }
+
+    //revoke the minting and metadata rights of a game contract (only the contract owner)
+    pub fn remove_authorized_minter(&mut self, account_id: AccountId) {
+        self.assert_owner();
+        self.authorized_minters.remove(&account_id);
+    }
+
+    //check if the passed in account is allowed to mint and mutate token metadata
+    pub fn is_authorized_minter(&self, account_id: AccountId) -> bool {
+        self.authorized_minters.contains(&account_id)
+    }
+
+    //get the list of accounts allowed to mint and mutate token metadata
+    pub fn get_authorized_minters(&self) -> Vec<AccountId> {
+        self.authorized_minters.to_vec()
+    }
+}
+
+impl Contract {
+    //make sure that the caller is the owner of the contract
+    pub(crate) fn assert_owner(&self) {
+        assert_eq!(
+            env::predecessor_account_id(),
+            self.owner_id,
+            "Only the contract owner can call this method"
+        );
+    }
+
+    //make sure that the caller is one of the authorized game contracts
+    pub(crate) fn assert_authorized_minter(&self) {
+        assert!(
+            self.authorized_minters
+                .contains(&env::predecessor_account_id()),
+            "Only authorized minters can call this method"
+        );
+    }
+}
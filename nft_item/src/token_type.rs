@@ -0,0 +1,37 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //lock a token type so that none of its tokens can be transferred or approved (only the contract owner)
+    pub fn lock_token_type(&mut self, token_type: TokenType) {
+        self.assert_owner();
+        self.token_types_locked.insert(&token_type);
+    }
+
+    //allow the tokens of a locked token type to be transferred again (only the contract owner)
+    pub fn unlock_token_type(&mut self, token_type: TokenType) {
+        self.assert_owner();
+        self.token_types_locked.remove(&token_type);
+    }
+
+    //check if the tokens of the passed in type can't be transferred
+    pub fn is_token_type_locked(&self, token_type: TokenType) -> bool {
+        self.token_types_locked.contains(&token_type)
+    }
+
+    //get all the token types that are locked against transfers
+    pub fn get_locked_token_types(&self) -> Vec<TokenType> {
+        self.token_types_locked.to_vec()
+    }
+}
+
+impl Contract {
+    //make sure that the tokens of the passed in type can be transferred and approved
+    pub(crate) fn assert_token_type_not_locked(&self, token_type: &TokenType) {
+        assert!(
+            !self.token_types_locked.contains(token_type),
+            "Token type {:?} is locked",
+            token_type
+        );
+    }
+}
@@ -146,6 +146,9 @@ impl Contract {
         //get the token object by passing in the token_id
         let token = self.tokens_by_id.get(token_id).expect("No token");
 
+        //locked tokens can't be transferred until the game releases them
+        self.assert_not_locked(token_id);
+
         //if the sender doesn't equal the owner, we check if the sender is in the approval list
         if sender_id != &token.owner_id {
             //if the token's approved account IDs doesn't contain the sender, we panic
@@ -192,12 +195,39 @@ impl Contract {
         //insert that new token into the tokens_by_id, replacing the old entry
         self.tokens_by_id.insert(token_id, &new_token);
 
-        //if there was some memo attached, we log it.
-        if let Some(memo) = memo {
-            env::log_str(&format!("Memo: {}", memo).to_string());
+        // Default the authorized ID to be None for the logs.
+        let mut authorized_id = None;
+        //if the sender isn't the owner, they were approved to transfer the token
+        if sender_id != &token.owner_id {
+            authorized_id = Some(sender_id.to_string());
         }
 
+        // Construct the transfer log as per the events standard.
+        EventLog::nep171(EventLogVariant::NftTransfer(vec![NftTransferLog {
+            // The optional authorized account ID to transfer the token on behalf of the old owner.
+            authorized_id,
+            // The old owner's account ID.
+            old_owner_id: token.owner_id.to_string(),
+            // The account ID of the new owner of the token.
+            new_owner_id: receiver_id.to_string(),
+            // A vector containing the token IDs as strings.
+            token_ids: vec![token_id.to_string()],
+            // An optional memo to include.
+            memo,
+        }]))
+        .emit();
+
         //return the previous token object that was transferred.
         token
     }
+
+    //log a metadata update event for the given token, authorized by the caller
+    pub(crate) fn internal_emit_metadata_update(&self, token_id: TokenId) {
+        EventLog::nep171(EventLogVariant::NftMetadataUpdate(vec![NftMetadataUpdateLog {
+            authorized_id: env::predecessor_account_id().to_string(),
+            token_ids: vec![token_id],
+            memo: None,
+        }]))
+        .emit();
+    }
 }
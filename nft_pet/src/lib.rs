@@ -8,17 +8,27 @@ use near_sdk::{
 use std::collections::HashMap;
 
 pub use crate::approval::*;
+pub use crate::events::*;
 use crate::internal::*;
 pub use crate::metadata::*;
 pub use crate::mint::*;
 
 mod approval;
 mod enumeration;
+mod events;
 mod internal;
+mod lock;
 mod metadata;
+mod migrate;
 mod mint;
+mod minter;
 mod nft_core;
 
+/// This is the name of the NFT standard we're using
+pub const NFT_STANDARD_NAME: &str = "nep171";
+/// This is the version of the NFT events standard we're using
+pub const NFT_EVENT_VERSION: &str = "1.0.0";
+
 #[near_bindgen]
 #[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
 pub struct Contract {
@@ -36,6 +46,12 @@ pub struct Contract {
 
     //keeps track of the metadata for the contract
     pub metadata: LazyOption<NFTContractMetadata>,
+
+    //game contracts allowed to mint tokens and mutate token metadata
+    pub authorized_minters: UnorderedSet<AccountId>,
+
+    //keeps track of the game locks for a given token ID
+    pub token_locks: LookupMap<TokenId, TokenLock>,
 }
 
 /// Helper structure for keys of the persistent collections.
@@ -49,6 +65,8 @@ pub enum StorageKey {
     TokensPerType,
     TokensPerTypeInner { token_type_hash: CryptoHash },
     TokenTypesLocked,
+    AuthorizedMinters,
+    TokenLocks,
 }
 
 #[near_bindgen]
@@ -59,7 +77,7 @@ impl Contract {
         user doesn't have to manually type metadata.
     */
     #[init]
-    pub fn new_default_meta(owner_id: AccountId) -> Self {
+    pub fn new_default_meta(owner_id: AccountId, game_id: Option<AccountId>) -> Self {
         //calls the other function "new: with some default metadata and the owner_id passed in
         Self::new(
             owner_id,
@@ -72,18 +90,19 @@ impl Contract {
                 reference: None,
                 reference_hash: None,
             },
+            game_id,
         )
     }
 
     /*
         initialization function (can only be called once).
         this initializes the contract with metadata that was passed in and
-        the owner_id.
+        the owner_id. The game contract (joy_v1) passed in is allowed to mint.
     */
     #[init]
-    pub fn new(owner_id: AccountId, metadata: NFTContractMetadata) -> Self {
+    pub fn new(owner_id: AccountId, metadata: NFTContractMetadata, game_id: Option<AccountId>) -> Self {
         //create a variable of type Self with all the fields initialized.
-        let this = Self {
+        let mut this = Self {
             //Storage keys are simply the prefixes used for the collections. This helps avoid data collision
             tokens_per_owner: LookupMap::new(StorageKey::TokensPerOwner.try_to_vec().unwrap()),
             tokens_by_id: LookupMap::new(StorageKey::TokensById.try_to_vec().unwrap()),
@@ -96,8 +115,16 @@ impl Contract {
                 StorageKey::NFTContractMetadata.try_to_vec().unwrap(),
                 Some(&metadata),
             ),
+            authorized_minters: UnorderedSet::new(
+                StorageKey::AuthorizedMinters.try_to_vec().unwrap(),
+            ),
+            token_locks: LookupMap::new(StorageKey::TokenLocks.try_to_vec().unwrap()),
         };
 
+        if let Some(game_id) = game_id {
+            this.authorized_minters.insert(&game_id);
+        }
+
         //return the Contract object
         this
     }
@@ -0,0 +1,70 @@
+use crate::*;
+
+#[near_bindgen]
+impl Contract {
+    //lock a token of the passed in owner so that it can't be transferred or approved while it's used by a game (only authorized minters)
+    #[payable]
+    pub fn nft_lock(&mut self, token_id: TokenId, owner_id: AccountId, reason: String) {
+        //assert that the caller attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+        self.assert_authorized_minter();
+
+        //get the token object and make sure it isn't locked already
+        let mut token = self.tokens_by_id.get(&token_id).expect("No token");
+        //the game must lock the token of the account it's acting for
+        assert_eq!(token.owner_id, owner_id, "Token is not owned by {}", owner_id);
+        if let Some(token_lock) = self.token_locks.get(&token_id) {
+            env::panic_str(&format!(
+                "Token is already locked by {} ({})",
+                token_lock.locked_by, token_lock.reason
+            ));
+        }
+
+        //a locked token can't be moved by anyone, so we clear the existing approvals
+        if !token.approved_account_ids.is_empty() {
+            refund_approved_account_ids(token.owner_id.clone(), &token.approved_account_ids);
+            token.approved_account_ids.clear();
+            self.tokens_by_id.insert(&token_id, &token);
+        }
+
+        let token_lock = TokenLock {
+            locked_by: env::predecessor_account_id(),
+            reason,
+            locked_at: env::block_timestamp(),
+        };
+        self.token_locks.insert(&token_id, &token_lock);
+    }
+
+    //release the lock of a token (only the account that locked it or the contract owner)
+    #[payable]
+    pub fn nft_unlock(&mut self, token_id: TokenId) {
+        //assert that the caller attached exactly 1 yoctoNEAR for security reasons
+        assert_one_yocto();
+
+        let token_lock = self.token_locks.get(&token_id).expect("Token is not locked");
+        let predecessor_account_id = env::predecessor_account_id();
+        assert!(
+            predecessor_account_id == token_lock.locked_by || predecessor_account_id == self.owner_id,
+            "Only the account that locked the token can unlock it"
+        );
+
+        self.token_locks.remove(&token_id);
+    }
+
+    //get why and by whom a token is locked, if it is locked
+    pub fn nft_lock_info(&self, token_id: TokenId) -> Option<TokenLock> {
+        self.token_locks.get(&token_id)
+    }
+}
+
+impl Contract {
+    //make sure that the token isn't locked by a game
+    pub(crate) fn assert_not_locked(&self, token_id: &TokenId) {
+        if let Some(token_lock) = self.token_locks.get(token_id) {
+            env::panic_str(&format!(
+                "Token is locked by {} ({})",
+                token_lock.locked_by, token_lock.reason
+            ));
+        }
+    }
+}
@@ -71,6 +71,18 @@ pub struct Token {
     pub next_approval_id: u64,
 }
 
+//A lock placed on a token by a game contract while the token is staked, equipped or in use
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[serde(crate = "near_sdk::serde")]
+pub struct TokenLock {
+    //the game contract that locked the token
+    pub locked_by: AccountId,
+    //why the token is locked, e.g. "staking" or "mining"
+    pub reason: String,
+    //when the token was locked, Unix epoch in nanoseconds
+    pub locked_at: u64,
+}
+
 //The Json token is what will be returned from view calls.
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
@@ -0,0 +1,42 @@
+use crate::*;
+
+//the contract state before game minters and token locks were added
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct OldContract {
+    pub owner_id: AccountId,
+    pub tokens_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    pub tokens_by_id: LookupMap<TokenId, Token>,
+    pub token_metadata_by_id: UnorderedMap<TokenId, TokenMetadata>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+}
+
+#[near_bindgen]
+impl Contract {
+    /*
+        migrate the state of a contract deployed before game minters and token locks.
+        the game contract (joy_v1) passed in is allowed to mint, others are added with add_authorized_minter.
+    */
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate(game_id: Option<AccountId>) -> Self {
+        let old_state: OldContract = env::state_read().expect("No state to migrate");
+
+        let mut this = Self {
+            owner_id: old_state.owner_id,
+            tokens_per_owner: old_state.tokens_per_owner,
+            tokens_by_id: old_state.tokens_by_id,
+            token_metadata_by_id: old_state.token_metadata_by_id,
+            metadata: old_state.metadata,
+            authorized_minters: UnorderedSet::new(
+                StorageKey::AuthorizedMinters.try_to_vec().unwrap(),
+            ),
+            token_locks: LookupMap::new(StorageKey::TokenLocks.try_to_vec().unwrap()),
+        };
+
+        if let Some(game_id) = game_id {
+            this.authorized_minters.insert(&game_id);
+        }
+
+        this
+    }
+}
@@ -213,13 +213,28 @@ impl NonFungibleTokenResolver for Contract {
         token.owner_id = owner_id;
 
         //we refund the receiver any approved account IDs that they may have set on the token
-        refund_approved_account_ids(receiver_id, &token.approved_account_ids);
+        refund_approved_account_ids(receiver_id.clone(), &token.approved_account_ids);
         //reset the approved account IDs to what they were before the transfer
         token.approved_account_ids = approved_account_ids;
 
         //we inset the token back into the tokens_by_id collection
         self.tokens_by_id.insert(&token_id, &token);
 
+        // Construct the transfer log as per the events standard.
+        EventLog::nep171(EventLogVariant::NftTransfer(vec![NftTransferLog {
+            // The optional authorized account ID to transfer the token on behalf of the old owner.
+            authorized_id: None,
+            // The old owner's account ID.
+            old_owner_id: receiver_id.to_string(),
+            // The account ID of the new owner of the token.
+            new_owner_id: token.owner_id.to_string(),
+            // A vector containing the token IDs as strings.
+            token_ids: vec![token_id.to_string()],
+            // An optional memo to include.
+            memo: None,
+        }]))
+        .emit();
+
         //return false
         false
     }
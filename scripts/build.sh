@@ -3,10 +3,22 @@ set -e
 cd "`dirname $0`"/../ft_token
 cargo build --all --target wasm32-unknown-unknown --release
 cd ..
+cd "`dirname $0`"/../nft_pet
+cargo build --all --target wasm32-unknown-unknown --release
+cd ..
+cd "`dirname $0`"/../nft_item
+cargo build --all --target wasm32-unknown-unknown --release
+cd ..
 cd "`dirname $0`"/../faucet
 cargo build --all --target wasm32-unknown-unknown --release
 cd ..
 cd "`dirname $0`"/../joy_v1
 cargo build --all --target wasm32-unknown-unknown --release
 cd ..
+cd "`dirname $0`"/../marketplace
+cargo build --all --target wasm32-unknown-unknown --release
+cd ..
+cd "`dirname $0`"/../joy_swap
+cargo build --all --target wasm32-unknown-unknown --release
+cd ..
 cp ./target/wasm32-unknown-unknown/release/*.wasm ./res/
\ No newline at end of file